@@ -21,6 +21,15 @@ pub enum CreateInvitationError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`create_invitation_bulk`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CreateInvitationBulkError {
+	Status400(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`list_invitations`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -77,6 +86,43 @@ impl Invitation {
 		}
 	}
 
+	/// Use this API operation to create multiple invitations for the provided email addresses. You can choose to send the invitations as emails by setting the `notify` parameter to `true`. There cannot be an existing invitation for any of the email addresses you provide unless you set `ignore_existing` to `true` for specific email addresses. Either all of the invitations are created, or none of them is.
+	pub async fn create_invitation_bulk(
+		clerk_client: &Clerk,
+		create_invitation_request: Vec<crate::models::CreateInvitationRequest>,
+	) -> Result<Vec<crate::models::Invitation>, Error<CreateInvitationBulkError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!("{}/invitations/bulk", local_var_configuration.base_path);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&create_invitation_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<CreateInvitationBulkError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// Returns all non-revoked invitations for your application, sorted by creation date
 	pub async fn list_invitations(clerk_client: &Clerk, status: Option<&str>) -> Result<Vec<crate::models::Invitation>, Error<ListInvitationsError>> {
 		let local_var_configuration = &clerk_client.config;
@@ -148,3 +194,46 @@ impl Invitation {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{apis::test_clerk, models::CreateInvitationRequest};
+	use mockito::Matcher;
+
+	#[tokio::test]
+	async fn test_create_invitation_bulk() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v1/invitations/bulk")
+			.match_body(Matcher::Json(serde_json::json!([
+				{"email_address": "first@example.com", "notify": false},
+				{"email_address": "second@example.com", "ignore_existing": true}
+			])))
+			.with_body(
+				r#"[
+					{"object":"invitation","id":"inv_1","email_address":"first@example.com","status":"pending","created_at":1,"updated_at":1},
+					{"object":"invitation","id":"inv_2","email_address":"second@example.com","status":"pending","created_at":1,"updated_at":1}
+				]"#,
+			)
+			.create_async()
+			.await;
+		let clerk = test_clerk(server.url());
+
+		let invitations = vec![
+			CreateInvitationRequest {
+				notify: Some(false),
+				..CreateInvitationRequest::new("first@example.com".to_string())
+			},
+			CreateInvitationRequest {
+				ignore_existing: Some(true),
+				..CreateInvitationRequest::new("second@example.com".to_string())
+			},
+		];
+		let created = Invitation::create_invitation_bulk(&clerk, invitations).await.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(created.len(), 2);
+		assert_eq!(created[1].email_address, "second@example.com");
+	}
+}
@@ -67,7 +67,7 @@ impl Miscellaneous {
 		clerk_client: &Clerk,
 		frontend_api: Option<&str>,
 		publishable_key: Option<&str>,
-	) -> Result<(), Error<GetPublicInterstitialError>> {
+	) -> Result<String, Error<GetPublicInterstitialError>> {
 		let local_var_configuration = &clerk_client.config;
 
 		let local_var_client = &local_var_configuration.client;
@@ -92,7 +92,7 @@ impl Miscellaneous {
 		let local_var_content = local_var_resp.text().await?;
 
 		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
-			Ok(())
+			Ok(local_var_content)
 		} else {
 			let local_var_entity: Option<GetPublicInterstitialError> = serde_json::from_str(&local_var_content).ok();
 			let local_var_error = ResponseContent {
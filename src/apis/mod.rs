@@ -83,6 +83,17 @@ pub fn parse_deep_object(prefix: &str, value: &serde_json::Value) -> Vec<(String
 	unimplemented!("Only objects are supported with style=deepObject")
 }
 
+/// A client sending its requests to a mock server, e.g. to the url of a `mockito::Server`.
+#[cfg(test)]
+pub(crate) fn test_clerk(url: String) -> crate::clerk::Clerk {
+	let config = crate::ClerkConfiguration {
+		base_path: format!("{}/v1", url),
+		..Default::default()
+	};
+
+	crate::clerk::Clerk::new(config)
+}
+
 pub mod actor_tokens_api;
 pub mod allow_list_block_list_api;
 pub mod beta_features_api;
@@ -96,8 +107,11 @@ pub mod invitations_api;
 pub mod jwks_api;
 pub mod jwt_templates_api;
 pub mod miscellaneous_api;
+pub mod organization_domains_api;
 pub mod organization_invitations_api;
 pub mod organization_memberships_api;
+pub mod organization_permissions_api;
+pub mod organization_roles_api;
 pub mod organizations_api;
 pub mod phone_numbers_api;
 pub mod redirect_urls_api;
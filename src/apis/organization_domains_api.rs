@@ -0,0 +1,363 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+use reqwest;
+
+use super::Error;
+use crate::{apis::ResponseContent, clerk::Clerk};
+
+/// struct for typed errors of method [`create_organization_domain`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CreateOrganizationDomainError {
+	Status400(crate::models::ClerkErrors),
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`delete_organization_domain`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteOrganizationDomainError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`list_organization_domains`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListOrganizationDomainsError {
+	Status401(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`update_organization_domain`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UpdateOrganizationDomainError {
+	Status400(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+pub struct OrganizationDomain;
+
+impl OrganizationDomain {
+	/// Creates a new organization domain. By default the domain is verified, but can be optionally set to unverified. Users signing up with an email address on a verified domain can join the organization automatically, depending on the enrollment mode of the domain.
+	pub async fn create_organization_domain(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		create_organization_domain_request: crate::models::CreateOrganizationDomainRequest,
+	) -> Result<crate::models::OrganizationDomain, Error<CreateOrganizationDomainError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/domains",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&create_organization_domain_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<CreateOrganizationDomainError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Removes the given domain from the organization. Pending invitations and suggestions sent for the domain are revoked.
+	pub async fn delete_organization_domain(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		domain_id: &str,
+	) -> Result<crate::models::DeletedObject, Error<DeleteOrganizationDomainError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/domains/{domain_id}",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id),
+			domain_id = crate::apis::urlencode(domain_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteOrganizationDomainError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Get a list of all domains of an organization. Results can be paginated using the optional `limit` and `offset` query parameters, and filtered by their verification status and enrollment mode.
+	pub async fn list_organization_domains(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		limit: Option<u64>,
+		offset: Option<u64>,
+		verified: Option<bool>,
+		enrollment_mode: Option<crate::models::organization_domain::EnrollmentMode>,
+	) -> Result<crate::models::OrganizationDomains, Error<ListOrganizationDomainsError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/domains",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_str) = limit {
+			local_var_req_builder = local_var_req_builder.query(&[("limit", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = offset {
+			local_var_req_builder = local_var_req_builder.query(&[("offset", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = verified {
+			local_var_req_builder = local_var_req_builder.query(&[("verified", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = enrollment_mode {
+			local_var_req_builder = local_var_req_builder.query(&[("enrollment_mode", local_var_str.as_str())]);
+		}
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<ListOrganizationDomainsError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Updates the name, enrollment mode or verification status of an organization domain. Only verified domains can use the automatic enrollment modes.
+	pub async fn update_organization_domain(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		domain_id: &str,
+		update_organization_domain_request: crate::models::UpdateOrganizationDomainRequest,
+	) -> Result<crate::models::OrganizationDomain, Error<UpdateOrganizationDomainError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/domains/{domain_id}",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id),
+			domain_id = crate::apis::urlencode(domain_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::PATCH, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&update_organization_domain_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<UpdateOrganizationDomainError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		apis::{test_clerk, Error},
+		models::{organization_domain::EnrollmentMode, CreateOrganizationDomainRequest, UpdateOrganizationDomainRequest},
+	};
+	use mockito::Matcher;
+
+	const DOMAIN_BODY: &str = r#"{
+		"object": "organization_domain",
+		"id": "orgdmn_2Xt6WsbS1r2M1SIfaDcmV3Mq0fA",
+		"organization_id": "org_2Xt5wWZzRTgbZ2VZQPRQWXrXyLe",
+		"name": "example.com",
+		"enrollment_mode": "automatic_invitation",
+		"affiliation_email_address": null,
+		"verification": {"status": "verified", "strategy": "admin", "attempts": null, "expire_at": null},
+		"total_pending_invitations": 2,
+		"total_pending_suggestions": 0,
+		"created_at": 1700000000000,
+		"updated_at": 1700000000000
+	}"#;
+
+	#[tokio::test]
+	async fn test_create_organization_domain() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v1/organizations/org_2Xt5wWZzRTgbZ2VZQPRQWXrXyLe/domains")
+			.match_body(Matcher::Json(
+				serde_json::json!({"name": "example.com", "enrollment_mode": "automatic_invitation"}),
+			))
+			.with_body(DOMAIN_BODY)
+			.create_async()
+			.await;
+
+		let request = CreateOrganizationDomainRequest {
+			enrollment_mode: Some(EnrollmentMode::AutomaticInvitation),
+			..CreateOrganizationDomainRequest::new("example.com".to_string())
+		};
+		let domain = OrganizationDomain::create_organization_domain(&test_clerk(server.url()), "org_2Xt5wWZzRTgbZ2VZQPRQWXrXyLe", request)
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(domain.enrollment_mode, EnrollmentMode::AutomaticInvitation);
+		assert_eq!(domain.total_pending_invitations, 2);
+		assert!(domain.is_verified());
+	}
+
+	#[tokio::test]
+	async fn test_list_organization_domains() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/organizations/org_2Xt5wWZzRTgbZ2VZQPRQWXrXyLe/domains")
+			.match_query(Matcher::AllOf(vec![
+				Matcher::UrlEncoded("verified".into(), "true".into()),
+				Matcher::UrlEncoded("enrollment_mode".into(), "automatic_invitation".into()),
+			]))
+			.with_body(format!(r#"{{"data":[{}],"total_count":1}}"#, DOMAIN_BODY))
+			.create_async()
+			.await;
+
+		let domains = OrganizationDomain::list_organization_domains(
+			&test_clerk(server.url()),
+			"org_2Xt5wWZzRTgbZ2VZQPRQWXrXyLe",
+			None,
+			None,
+			Some(true),
+			Some(EnrollmentMode::AutomaticInvitation),
+		)
+		.await
+		.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(domains.total_count, 1);
+		assert_eq!(domains.data[0].name, "example.com");
+	}
+
+	#[tokio::test]
+	async fn test_update_organization_domain_error() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("PATCH", "/v1/organizations/org_1/domains/orgdmn_1")
+			.with_status(422)
+			.with_body(r#"{"errors":[{"message":"domain is not verified","long_message":"Only verified domains can use automatic enrollment","code":"organization_domain_not_verified"}]}"#)
+			.create_async()
+			.await;
+
+		let request = UpdateOrganizationDomainRequest {
+			enrollment_mode: Some(EnrollmentMode::AutomaticSuggestion),
+			..UpdateOrganizationDomainRequest::new()
+		};
+		match OrganizationDomain::update_organization_domain(&test_clerk(server.url()), "org_1", "orgdmn_1", request).await {
+			Err(Error::ResponseError(content)) => {
+				let errors = match content.entity {
+					Some(UpdateOrganizationDomainError::Status400(errors))
+					| Some(UpdateOrganizationDomainError::Status404(errors))
+					| Some(UpdateOrganizationDomainError::Status422(errors)) => errors,
+					entity => panic!("expected Clerk errors, got {entity:?}"),
+				};
+				assert_eq!(content.status, reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+				assert_eq!(errors.errors[0].code, "organization_domain_not_verified");
+			}
+			res => panic!("expected a response error, got {res:?}"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_delete_organization_domain() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("DELETE", "/v1/organizations/org_1/domains/orgdmn_1")
+			.with_body(r#"{"object":"organization_domain","id":"orgdmn_1","deleted":true}"#)
+			.create_async()
+			.await;
+
+		let deleted = OrganizationDomain::delete_organization_domain(&test_clerk(server.url()), "org_1", "orgdmn_1")
+			.await
+			.unwrap();
+		assert!(deleted.deleted);
+	}
+}
@@ -24,6 +24,36 @@ pub enum CreateOrganizationInvitationError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`create_organization_invitation_bulk`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CreateOrganizationInvitationBulkError {
+	Status400(crate::models::ClerkErrors),
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`get_organization_invitation`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetOrganizationInvitationError {
+	Status400(crate::models::ClerkErrors),
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`list_organization_invitations`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListOrganizationInvitationsError {
+	Status400(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`list_pending_organization_invitations`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -88,6 +118,140 @@ impl OrganizationInvitation {
 		}
 	}
 
+	/// Creates new organization invitations in bulk and sends out emails to the provided email addresses with a link to accept the invitation and join the organization. Each invitation can have its own `role`, `inviter_user_id`, `redirect_url` and metadata, like with [`create_organization_invitation`](Self::create_organization_invitation). Either all of the invitations are created, or none of them is.
+	pub async fn create_organization_invitation_bulk(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		create_organization_invitation_bulk_request_inner: Vec<crate::models::CreateOrganizationInvitationBulkRequestInner>,
+	) -> Result<crate::models::OrganizationInvitations, Error<CreateOrganizationInvitationBulkError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/invitations/bulk",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&create_organization_invitation_bulk_request_inner);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<CreateOrganizationInvitationBulkError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Use this request to get an existing organization invitation by ID.
+	pub async fn get_organization_invitation(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		invitation_id: &str,
+	) -> Result<crate::models::OrganizationInvitation, Error<GetOrganizationInvitationError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/invitations/{invitation_id}",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id),
+			invitation_id = crate::apis::urlencode(invitation_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<GetOrganizationInvitationError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// This request returns the list of organization invitations. Results can be paginated using the optional `limit` and `offset` query parameters, and filtered by their `status`. The invitations are ordered by descending creation date.
+	pub async fn list_organization_invitations(
+		clerk_client: &Clerk,
+		organization_id: &str,
+		limit: Option<u64>,
+		offset: Option<u64>,
+		status: Option<&str>,
+	) -> Result<crate::models::OrganizationInvitations, Error<ListOrganizationInvitationsError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organizations/{organization_id}/invitations",
+			local_var_configuration.base_path,
+			organization_id = crate::apis::urlencode(organization_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_str) = limit {
+			local_var_req_builder = local_var_req_builder.query(&[("limit", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = offset {
+			local_var_req_builder = local_var_req_builder.query(&[("offset", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = status {
+			local_var_req_builder = local_var_req_builder.query(&[("status", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<ListOrganizationInvitationsError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// This request returns the list of organization invitations with \"pending\" status. These are the organization invitations that can still be used to join the organization, but have not been accepted by the invited user yet. Results can be paginated using the optional `limit` and `offset` query parameters. The organization invitations are ordered by descending creation date. Most recent invitations will be returned first.
 	pub async fn list_pending_organization_invitations(
 		clerk_client: &Clerk,
@@ -179,3 +343,92 @@ impl OrganizationInvitation {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		apis::{test_clerk, Error},
+		models::CreateOrganizationInvitationBulkRequestInner,
+	};
+	use mockito::Matcher;
+
+	const INVITATION_BODY: &str = r#"{"id":"orginv_1","object":"organization_invitation","email_address":"invitee@example.com","role":"org:member","organization_id":"org_1","status":"pending","created_at":1,"updated_at":1}"#;
+
+	#[tokio::test]
+	async fn test_create_organization_invitation_bulk() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v1/organizations/org_1/invitations/bulk")
+			.match_body(Matcher::Json(serde_json::json!([
+				{"email_address": "invitee@example.com", "inviter_user_id": "user_1", "role": "org:member"},
+				{"email_address": "admin@example.com", "inviter_user_id": "user_1", "role": "org:admin"}
+			])))
+			.with_body(format!(r#"{{"data":[{}],"total_count":1}}"#, INVITATION_BODY))
+			.create_async()
+			.await;
+
+		let invitations = [("invitee@example.com", "org:member"), ("admin@example.com", "org:admin")]
+			.map(|(email_address, role)| {
+				CreateOrganizationInvitationBulkRequestInner::new(email_address.to_string(), "user_1".to_string(), role.to_string())
+			})
+			.to_vec();
+		let created = OrganizationInvitation::create_organization_invitation_bulk(&test_clerk(server.url()), "org_1", invitations)
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(created.data[0].email_address.as_deref(), Some("invitee@example.com"));
+	}
+
+	#[tokio::test]
+	async fn test_list_organization_invitations_by_status() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/organizations/org_1/invitations")
+			.match_query(Matcher::AllOf(vec![
+				Matcher::UrlEncoded("status".into(), "accepted".into()),
+				Matcher::UrlEncoded("limit".into(), "20".into()),
+			]))
+			.with_body(r#"{"data":[],"total_count":0}"#)
+			.create_async()
+			.await;
+
+		let invitations = OrganizationInvitation::list_organization_invitations(&test_clerk(server.url()), "org_1", Some(20), None, Some("accepted"))
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(invitations.total_count, 0);
+	}
+
+	#[tokio::test]
+	async fn test_get_organization_invitation() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("GET", "/v1/organizations/org_1/invitations/orginv_1")
+			.with_body(INVITATION_BODY)
+			.create_async()
+			.await;
+		server
+			.mock("GET", "/v1/organizations/org_1/invitations/orginv_2")
+			.with_status(404)
+			.with_body(r#"{"errors":[{"message":"not found","long_message":"Invitation not found","code":"resource_not_found"}]}"#)
+			.create_async()
+			.await;
+		let clerk = test_clerk(server.url());
+
+		let invitation = OrganizationInvitation::get_organization_invitation(&clerk, "org_1", "orginv_1")
+			.await
+			.unwrap();
+		assert_eq!(invitation.status.as_deref(), Some("pending"));
+
+		match OrganizationInvitation::get_organization_invitation(&clerk, "org_1", "orginv_2").await {
+			Err(Error::ResponseError(content)) => {
+				assert_eq!(content.status, reqwest::StatusCode::NOT_FOUND);
+				assert!(content.entity.is_some());
+			}
+			res => panic!("expected a response error, got {res:?}"),
+		}
+	}
+}
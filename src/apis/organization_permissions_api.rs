@@ -0,0 +1,331 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+use reqwest;
+
+use super::Error;
+use crate::{apis::ResponseContent, clerk::Clerk};
+
+/// struct for typed errors of method [`create_organization_permission`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CreateOrganizationPermissionError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`delete_organization_permission`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteOrganizationPermissionError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`get_organization_permission`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetOrganizationPermissionError {
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`list_organization_permissions`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListOrganizationPermissionsError {
+	Status401(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`update_organization_permission`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UpdateOrganizationPermissionError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+pub struct OrganizationPermission;
+
+impl OrganizationPermission {
+	/// Creates a new custom organization permission. The key must follow the `org:<feature>:<action>` format and be unique for the instance.
+	pub async fn create_organization_permission(
+		clerk_client: &Clerk,
+		create_organization_permission_request: crate::models::CreateOrganizationPermissionRequest,
+	) -> Result<crate::models::OrganizationPermission, Error<CreateOrganizationPermissionError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!("{}/organization_permissions", local_var_configuration.base_path);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&create_organization_permission_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<CreateOrganizationPermissionError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Deletes the given organization permission, removing it from every role it was assigned to. System permissions can't be deleted.
+	pub async fn delete_organization_permission(
+		clerk_client: &Clerk,
+		organization_permission_id: &str,
+	) -> Result<crate::models::DeletedObject, Error<DeleteOrganizationPermissionError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_permissions/{organization_permission_id}",
+			local_var_configuration.base_path,
+			organization_permission_id = crate::apis::urlencode(organization_permission_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteOrganizationPermissionError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Fetches the organization permission with the given ID.
+	pub async fn get_organization_permission(
+		clerk_client: &Clerk,
+		organization_permission_id: &str,
+	) -> Result<crate::models::OrganizationPermission, Error<GetOrganizationPermissionError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_permissions/{organization_permission_id}",
+			local_var_configuration.base_path,
+			organization_permission_id = crate::apis::urlencode(organization_permission_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<GetOrganizationPermissionError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// This request returns the list of organization permissions for an instance. Results can be paginated using the optional `limit` and `offset` query parameters, searched with `query` on their name or key, and ordered with `order_by`.
+	pub async fn list_organization_permissions(
+		clerk_client: &Clerk,
+		limit: Option<u64>,
+		offset: Option<u64>,
+		query: Option<&str>,
+		order_by: Option<&str>,
+	) -> Result<crate::models::OrganizationPermissions, Error<ListOrganizationPermissionsError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!("{}/organization_permissions", local_var_configuration.base_path);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_str) = limit {
+			local_var_req_builder = local_var_req_builder.query(&[("limit", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = offset {
+			local_var_req_builder = local_var_req_builder.query(&[("offset", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = query {
+			local_var_req_builder = local_var_req_builder.query(&[("query", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = order_by {
+			local_var_req_builder = local_var_req_builder.query(&[("order_by", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<ListOrganizationPermissionsError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Updates the given organization permission. System permissions can't be updated.
+	pub async fn update_organization_permission(
+		clerk_client: &Clerk,
+		organization_permission_id: &str,
+		update_organization_permission_request: crate::models::UpdateOrganizationPermissionRequest,
+	) -> Result<crate::models::OrganizationPermission, Error<UpdateOrganizationPermissionError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_permissions/{organization_permission_id}",
+			local_var_configuration.base_path,
+			organization_permission_id = crate::apis::urlencode(organization_permission_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::PATCH, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&update_organization_permission_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<UpdateOrganizationPermissionError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		apis::{test_clerk, Error},
+		models::organization_permission::Type,
+	};
+	use mockito::Matcher;
+
+	#[tokio::test]
+	async fn test_list_organization_permissions() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/organization_permissions")
+			.match_query(Matcher::AllOf(vec![
+				Matcher::UrlEncoded("offset".into(), "10".into()),
+				Matcher::UrlEncoded("query".into(), "invoices".into()),
+			]))
+			.with_body(
+				r#"{"data":[{"object":"permission","id":"perm_1","name":"Manage members","key":"org:sys_memberships:manage","description":"","type":"system","created_at":1,"updated_at":1}],"total_count":11}"#,
+			)
+			.create_async()
+			.await;
+
+		let permissions = OrganizationPermission::list_organization_permissions(&test_clerk(server.url()), None, Some(10), Some("invoices"), None)
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(permissions.total_count, 11);
+		assert_eq!(permissions.data[0].r#type, Type::System);
+	}
+
+	#[tokio::test]
+	async fn test_update_organization_permission_error() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("PATCH", "/v1/organization_permissions/perm_1")
+			.with_status(404)
+			.with_body(r#"{"errors":[{"message":"not found","long_message":"Permission not found","code":"resource_not_found"}]}"#)
+			.create_async()
+			.await;
+
+		let request = crate::models::UpdateOrganizationPermissionRequest {
+			name: Some("Read invoices".to_string()),
+			..Default::default()
+		};
+		match OrganizationPermission::update_organization_permission(&test_clerk(server.url()), "perm_1", request).await {
+			Err(Error::ResponseError(content)) => {
+				assert_eq!(content.status, reqwest::StatusCode::NOT_FOUND);
+				assert!(content.entity.is_some());
+			}
+			res => panic!("expected a response error, got {res:?}"),
+		}
+	}
+}
@@ -0,0 +1,495 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+use reqwest;
+
+use super::Error;
+use crate::{apis::ResponseContent, clerk::Clerk};
+
+/// struct for typed errors of method [`assign_permission_to_organization_role`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AssignPermissionToOrganizationRoleError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`create_organization_role`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CreateOrganizationRoleError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`delete_organization_role`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteOrganizationRoleError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`get_organization_role`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetOrganizationRoleError {
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`list_organization_roles`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListOrganizationRolesError {
+	Status401(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`remove_permission_from_organization_role`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RemovePermissionFromOrganizationRoleError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`update_organization_role`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UpdateOrganizationRoleError {
+	Status400(crate::models::ClerkErrors),
+	Status401(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+pub struct OrganizationRole;
+
+impl OrganizationRole {
+	/// Adds the given permission to the organization role.
+	pub async fn assign_permission_to_organization_role(
+		clerk_client: &Clerk,
+		organization_role_id: &str,
+		permission_id: &str,
+	) -> Result<crate::models::OrganizationRole, Error<AssignPermissionToOrganizationRoleError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_roles/{organization_role_id}/permissions/{permission_id}",
+			local_var_configuration.base_path,
+			organization_role_id = crate::apis::urlencode(organization_role_id),
+			permission_id = crate::apis::urlencode(permission_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<AssignPermissionToOrganizationRoleError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Creates a new custom organization role. The key must start with `org:` and be unique for the instance. The role is granted the given permissions.
+	pub async fn create_organization_role(
+		clerk_client: &Clerk,
+		create_organization_role_request: crate::models::CreateOrganizationRoleRequest,
+	) -> Result<crate::models::OrganizationRole, Error<CreateOrganizationRoleError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!("{}/organization_roles", local_var_configuration.base_path);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&create_organization_role_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<CreateOrganizationRoleError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Deletes the given organization role. Roles that are still assigned to members, or set as the creator or default role of the instance, can't be deleted.
+	pub async fn delete_organization_role(
+		clerk_client: &Clerk,
+		organization_role_id: &str,
+	) -> Result<crate::models::DeletedObject, Error<DeleteOrganizationRoleError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_roles/{organization_role_id}",
+			local_var_configuration.base_path,
+			organization_role_id = crate::apis::urlencode(organization_role_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteOrganizationRoleError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Fetches the organization role with the given ID.
+	pub async fn get_organization_role(
+		clerk_client: &Clerk,
+		organization_role_id: &str,
+	) -> Result<crate::models::OrganizationRole, Error<GetOrganizationRoleError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_roles/{organization_role_id}",
+			local_var_configuration.base_path,
+			organization_role_id = crate::apis::urlencode(organization_role_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<GetOrganizationRoleError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// This request returns the list of organization roles for an instance. Results can be paginated using the optional `limit` and `offset` query parameters, searched with `query` on their name or key, and ordered with `order_by`.
+	pub async fn list_organization_roles(
+		clerk_client: &Clerk,
+		limit: Option<u64>,
+		offset: Option<u64>,
+		query: Option<&str>,
+		order_by: Option<&str>,
+	) -> Result<crate::models::OrganizationRoles, Error<ListOrganizationRolesError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!("{}/organization_roles", local_var_configuration.base_path);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_str) = limit {
+			local_var_req_builder = local_var_req_builder.query(&[("limit", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = offset {
+			local_var_req_builder = local_var_req_builder.query(&[("offset", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = query {
+			local_var_req_builder = local_var_req_builder.query(&[("query", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = order_by {
+			local_var_req_builder = local_var_req_builder.query(&[("order_by", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<ListOrganizationRolesError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Removes the given permission from the organization role.
+	pub async fn remove_permission_from_organization_role(
+		clerk_client: &Clerk,
+		organization_role_id: &str,
+		permission_id: &str,
+	) -> Result<crate::models::OrganizationRole, Error<RemovePermissionFromOrganizationRoleError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_roles/{organization_role_id}/permissions/{permission_id}",
+			local_var_configuration.base_path,
+			organization_role_id = crate::apis::urlencode(organization_role_id),
+			permission_id = crate::apis::urlencode(permission_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<RemovePermissionFromOrganizationRoleError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Updates the given organization role. Passing `permissions` replaces all the permissions of the role.
+	pub async fn update_organization_role(
+		clerk_client: &Clerk,
+		organization_role_id: &str,
+		update_organization_role_request: crate::models::UpdateOrganizationRoleRequest,
+	) -> Result<crate::models::OrganizationRole, Error<UpdateOrganizationRoleError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/organization_roles/{organization_role_id}",
+			local_var_configuration.base_path,
+			organization_role_id = crate::apis::urlencode(organization_role_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::PATCH, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		local_var_req_builder = local_var_req_builder.json(&update_organization_role_request);
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<UpdateOrganizationRoleError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		apis::{test_clerk, Error},
+		models::CreateOrganizationRoleRequest,
+	};
+	use mockito::Matcher;
+
+	const ROLE_BODY: &str = r#"{
+		"object": "role",
+		"id": "role_2aQhBaMTLkP3wYjgV9eMPkDOY1B",
+		"name": "Billing manager",
+		"key": "org:billing_manager",
+		"description": "Manages the invoices of the organization",
+		"is_creator_eligible": false,
+		"permissions": [
+			{
+				"object": "permission",
+				"id": "perm_2aQhBcn6ddbPhQ5xtWRo7vIlRWq",
+				"name": "Read invoices",
+				"key": "org:invoices:read",
+				"description": null,
+				"type": "user",
+				"created_at": 1700000000000,
+				"updated_at": 1700000000000
+			}
+		],
+		"created_at": 1700000000000,
+		"updated_at": 1700000000000
+	}"#;
+
+	#[tokio::test]
+	async fn test_list_organization_roles() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/organization_roles")
+			.match_query(Matcher::AllOf(vec![
+				Matcher::UrlEncoded("limit".into(), "50".into()),
+				Matcher::UrlEncoded("query".into(), "billing".into()),
+				Matcher::UrlEncoded("order_by".into(), "+name".into()),
+			]))
+			.with_body(format!(r#"{{"data":[{}],"total_count":1}}"#, ROLE_BODY))
+			.create_async()
+			.await;
+
+		let roles = OrganizationRole::list_organization_roles(&test_clerk(server.url()), Some(50), None, Some("billing"), Some("+name"))
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(roles.total_count, 1);
+		assert_eq!(roles.data[0].key, "org:billing_manager");
+		assert_eq!(roles.data[0].permissions[0].key, "org:invoices:read");
+	}
+
+	#[tokio::test]
+	async fn test_create_organization_role() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v1/organization_roles")
+			.match_body(Matcher::Json(serde_json::json!({
+				"name": "Billing manager",
+				"key": "org:billing_manager",
+				"permissions": ["perm_2aQhBcn6ddbPhQ5xtWRo7vIlRWq"]
+			})))
+			.with_body(ROLE_BODY)
+			.create_async()
+			.await;
+
+		let request = CreateOrganizationRoleRequest {
+			permissions: Some(vec!["perm_2aQhBcn6ddbPhQ5xtWRo7vIlRWq".to_string()]),
+			..CreateOrganizationRoleRequest::new("Billing manager".to_string(), "org:billing_manager".to_string())
+		};
+		let role = OrganizationRole::create_organization_role(&test_clerk(server.url()), request)
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(role.id, "role_2aQhBaMTLkP3wYjgV9eMPkDOY1B");
+	}
+
+	#[tokio::test]
+	async fn test_assign_permission_to_organization_role() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v1/organization_roles/role_1/permissions/perm_1")
+			.with_body(ROLE_BODY)
+			.create_async()
+			.await;
+
+		OrganizationRole::assign_permission_to_organization_role(&test_clerk(server.url()), "role_1", "perm_1")
+			.await
+			.unwrap();
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_delete_organization_role_error() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("DELETE", "/v1/organization_roles/role_1")
+			.with_status(400)
+			.with_body(r#"{"errors":[{"message":"role is assigned","long_message":"The role is still assigned to members","code":"organization_role_assigned_to_members"}]}"#)
+			.create_async()
+			.await;
+
+		match OrganizationRole::delete_organization_role(&test_clerk(server.url()), "role_1").await {
+			Err(Error::ResponseError(content)) => {
+				let Some(DeleteOrganizationRoleError::Status400(errors)) = content.entity else {
+					panic!("expected Clerk errors, got {:?}", content.entity);
+				};
+				assert_eq!(errors.errors[0].code, "organization_role_assigned_to_members");
+			}
+			res => panic!("expected a response error, got {res:?}"),
+		}
+	}
+}
@@ -31,6 +31,45 @@ pub enum CreateUserError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`delete_backup_code`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteBackupCodeError {
+	Status404(crate::models::ClerkErrors),
+	Status500(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`delete_external_account`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteExternalAccountError {
+	Status400(crate::models::ClerkErrors),
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status500(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`delete_passkey`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeletePasskeyError {
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status500(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`delete_totp`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteTotpError {
+	Status404(crate::models::ClerkErrors),
+	Status500(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`delete_user`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -41,6 +80,17 @@ pub enum DeleteUserError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`delete_web3_wallet`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeleteWeb3WalletError {
+	Status400(crate::models::ClerkErrors),
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	Status500(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`disable_mfa`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -86,6 +136,23 @@ pub enum GetUsersCountError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`list_o_auth_access_tokens`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListOAuthAccessTokensError {
+	Status400(crate::models::ClerkErrors),
+	Status422(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`lock_user`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LockUserError {
+	Status403(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`unban_user`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -94,6 +161,14 @@ pub enum UnbanUserError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`unlock_user`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UnlockUserError {
+	Status403(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`update_user`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -116,6 +191,16 @@ pub enum UpdateUserMetadataError {
 	UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`users_get_organization_invitations`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UsersGetOrganizationInvitationsError {
+	Status400(crate::models::ClerkErrors),
+	Status403(crate::models::ClerkErrors),
+	Status404(crate::models::ClerkErrors),
+	UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`users_get_organization_memberships`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -222,6 +307,163 @@ impl User {
 		}
 	}
 
+	/// Deletes the backup codes of the given user.
+	pub async fn delete_backup_code(
+		clerk_client: &Clerk,
+		user_id: &str,
+	) -> Result<crate::models::DeleteBackupCode200Response, Error<DeleteBackupCodeError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/backup_code",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteBackupCodeError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Deletes the given external account of the user, e.g. to unlink an OAuth provider.
+	pub async fn delete_external_account(
+		clerk_client: &Clerk,
+		user_id: &str,
+		external_account_id: &str,
+	) -> Result<crate::models::DeletedObject, Error<DeleteExternalAccountError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/external_accounts/{external_account_id}",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id),
+			external_account_id = crate::apis::urlencode(external_account_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteExternalAccountError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Deletes the passkey identification of the given user.
+	pub async fn delete_passkey(
+		clerk_client: &Clerk,
+		user_id: &str,
+		passkey_identification_id: &str,
+	) -> Result<crate::models::DeletedObject, Error<DeletePasskeyError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/passkeys/{passkey_identification_id}",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id),
+			passkey_identification_id = crate::apis::urlencode(passkey_identification_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeletePasskeyError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Deletes all of the TOTPs of the given user, e.g. when they lost access to their authenticator app.
+	pub async fn delete_totp(clerk_client: &Clerk, user_id: &str) -> Result<crate::models::DeleteTotp200Response, Error<DeleteTotpError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/totp",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteTotpError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// Delete the specified user
 	pub async fn delete_user(clerk_client: &Clerk, user_id: &str) -> Result<crate::models::DeletedObject, Error<DeleteUserError>> {
 		let local_var_configuration = &clerk_client.config;
@@ -258,6 +500,47 @@ impl User {
 		}
 	}
 
+	/// Deletes the web3 wallet identification of the given user.
+	pub async fn delete_web3_wallet(
+		clerk_client: &Clerk,
+		user_id: &str,
+		web3_wallet_identification_id: &str,
+	) -> Result<crate::models::DeletedObject, Error<DeleteWeb3WalletError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/web3_wallets/{web3_wallet_identification_id}",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id),
+			web3_wallet_identification_id = crate::apis::urlencode(web3_wallet_identification_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::DELETE, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<DeleteWeb3WalletError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// Disable all of a user's MFA methods (e.g. OTP sent via SMS, TOTP on their authenticator app) at once.
 	pub async fn disable_mfa(clerk_client: &Clerk, user_id: &str) -> Result<crate::models::DisableMfa200Response, Error<DisableMfaError>> {
 		let local_var_configuration = &clerk_client.config;
@@ -566,6 +849,92 @@ impl User {
 		}
 	}
 
+	/// Fetch a page of the OAuth access tokens of a user for a particular OAuth provider, along with their total count. Results can be paginated using the optional `limit` and `offset` query parameters. Expired access tokens are refreshed like [`get_o_auth_access_token`](Self::get_o_auth_access_token) does.
+	pub async fn list_o_auth_access_tokens(
+		clerk_client: &Clerk,
+		user_id: &str,
+		provider: &str,
+		limit: Option<u64>,
+		offset: Option<u64>,
+	) -> Result<crate::models::OAuthAccessTokens, Error<ListOAuthAccessTokensError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/oauth_access_tokens/{provider}",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id),
+			provider = crate::apis::urlencode(provider)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		local_var_req_builder = local_var_req_builder.query(&[("paginated", "true")]);
+		if let Some(ref local_var_str) = limit {
+			local_var_req_builder = local_var_req_builder.query(&[("limit", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = offset {
+			local_var_req_builder = local_var_req_builder.query(&[("offset", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<ListOAuthAccessTokensError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
+	/// Marks the given user as locked, which means they are not allowed to sign in again until the lock expires. Lock duration can be configured in the instance's restrictions settings.
+	pub async fn lock_user(clerk_client: &Clerk, user_id: &str) -> Result<crate::models::User, Error<LockUserError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/lock",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<LockUserError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// Removes the ban mark from the given user.
 	pub async fn unban_user(clerk_client: &Clerk, user_id: &str) -> Result<crate::models::User, Error<UnbanUserError>> {
 		let local_var_configuration = &clerk_client.config;
@@ -602,6 +971,42 @@ impl User {
 		}
 	}
 
+	/// Removes the lock from the given user, letting them sign in again.
+	pub async fn unlock_user(clerk_client: &Clerk, user_id: &str) -> Result<crate::models::User, Error<UnlockUserError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/unlock",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<UnlockUserError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// Update a user's attributes.  You can set the user's primary contact identifiers (email address and phone numbers) by updating the `primary_email_address_id` and `primary_phone_number_id` attributes respectively. Both IDs should correspond to verified identifications that belong to the user.  You can remove a user's username by setting the username attribute to null or the blank string \"\". This is a destructive action; the identification will be deleted forever. Usernames can be removed only if they are optional in your instance settings and there's at least one other identifier which can be used for authentication.
 	pub async fn update_user(
 		clerk_client: &Clerk,
@@ -686,6 +1091,57 @@ impl User {
 		}
 	}
 
+	/// Retrieve a paginated list of the organization invitations sent to the email addresses of the user, optionally filtered by their `status`.
+	pub async fn users_get_organization_invitations(
+		clerk_client: &Clerk,
+		user_id: &str,
+		limit: Option<u64>,
+		offset: Option<u64>,
+		status: Option<&str>,
+	) -> Result<crate::models::OrganizationInvitations, Error<UsersGetOrganizationInvitationsError>> {
+		let local_var_configuration = &clerk_client.config;
+
+		let local_var_client = &local_var_configuration.client;
+
+		let local_var_uri_str = format!(
+			"{}/users/{user_id}/organization_invitations",
+			local_var_configuration.base_path,
+			user_id = crate::apis::urlencode(user_id)
+		);
+		let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+		if let Some(ref local_var_str) = limit {
+			local_var_req_builder = local_var_req_builder.query(&[("limit", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = offset {
+			local_var_req_builder = local_var_req_builder.query(&[("offset", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_str) = status {
+			local_var_req_builder = local_var_req_builder.query(&[("status", &local_var_str.to_string())]);
+		}
+		if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+			local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+		}
+
+		let local_var_req = local_var_req_builder.build()?;
+		let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+		let local_var_status = local_var_resp.status();
+		let local_var_content = local_var_resp.text().await?;
+
+		if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+			serde_json::from_str(&local_var_content).map_err(Error::from)
+		} else {
+			let local_var_entity: Option<UsersGetOrganizationInvitationsError> = serde_json::from_str(&local_var_content).ok();
+			let local_var_error = ResponseContent {
+				status: local_var_status,
+				content: local_var_content,
+				entity: local_var_entity,
+			};
+			Err(Error::ResponseError(local_var_error))
+		}
+	}
+
 	/// Retrieve a paginated list of the user's organization memberships
 	pub async fn users_get_organization_memberships(
 		clerk_client: &Clerk,
@@ -817,3 +1273,134 @@ impl User {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::apis::{test_clerk, Error};
+	use mockito::Matcher;
+
+	#[tokio::test]
+	async fn test_lock_and_unlock_user() {
+		let mut server = mockito::Server::new_async().await;
+		let lock = server
+			.mock("POST", "/v1/users/user_1/lock")
+			.with_body(r#"{"id":"user_1","object":"user","locked":true,"lockout_expires_in_seconds":3600}"#)
+			.create_async()
+			.await;
+		let unlock = server
+			.mock("POST", "/v1/users/user_1/unlock")
+			.with_body(r#"{"id":"user_1","object":"user","locked":false}"#)
+			.create_async()
+			.await;
+		let clerk = test_clerk(server.url());
+
+		let user = User::lock_user(&clerk, "user_1").await.unwrap();
+		assert_eq!(user.locked, Some(true));
+		assert_eq!(user.lockout_expires_in_seconds, Some(3600));
+
+		let user = User::unlock_user(&clerk, "user_1").await.unwrap();
+		assert_eq!(user.locked, Some(false));
+		lock.assert_async().await;
+		unlock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_delete_second_factors() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("DELETE", "/v1/users/user_1/totp")
+			.with_body(r#"{"user_id":"user_1"}"#)
+			.create_async()
+			.await;
+		server
+			.mock("DELETE", "/v1/users/user_1/backup_code")
+			.with_status(404)
+			.with_body(r#"{"errors":[{"message":"not found","long_message":"No backup codes found","code":"resource_not_found"}]}"#)
+			.create_async()
+			.await;
+		let clerk = test_clerk(server.url());
+
+		let deleted = User::delete_totp(&clerk, "user_1").await.unwrap();
+		assert_eq!(deleted.user_id.as_deref(), Some("user_1"));
+
+		match User::delete_backup_code(&clerk, "user_1").await {
+			Err(Error::ResponseError(content)) => {
+				let Some(DeleteBackupCodeError::Status404(errors)) = content.entity else {
+					panic!("expected Clerk errors, got {:?}", content.entity);
+				};
+				assert_eq!(errors.errors[0].code, "resource_not_found");
+			}
+			res => panic!("expected a response error, got {res:?}"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_delete_identifications() {
+		let mut server = mockito::Server::new_async().await;
+		let mut mocks = Vec::new();
+		for (path, object) in [
+			("/v1/users/user_1/passkeys/idn_1", "passkey"),
+			("/v1/users/user_1/external_accounts/eac_1", "external_account"),
+			("/v1/users/user_1/web3_wallets/idn_2", "web3_wallet"),
+		] {
+			let mock = server
+				.mock("DELETE", path)
+				.with_body(format!(r#"{{"object":"{}","deleted":true}}"#, object))
+				.create_async()
+				.await;
+			mocks.push(mock);
+		}
+		let clerk = test_clerk(server.url());
+
+		assert!(User::delete_passkey(&clerk, "user_1", "idn_1").await.unwrap().deleted);
+		assert!(User::delete_external_account(&clerk, "user_1", "eac_1").await.unwrap().deleted);
+		assert!(User::delete_web3_wallet(&clerk, "user_1", "idn_2").await.unwrap().deleted);
+		for mock in mocks {
+			mock.assert_async().await;
+		}
+	}
+
+	#[tokio::test]
+	async fn test_list_o_auth_access_tokens() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/users/user_1/oauth_access_tokens/oauth_google")
+			.match_query(Matcher::AllOf(vec![
+				Matcher::UrlEncoded("paginated".into(), "true".into()),
+				Matcher::UrlEncoded("limit".into(), "1".into()),
+				Matcher::UrlEncoded("offset".into(), "1".into()),
+			]))
+			.with_body(r#"{"data":[{"object":"oauth_access_token","token":"ya29.a0","provider":"oauth_google","scopes":["email"]}],"total_count":2}"#)
+			.create_async()
+			.await;
+
+		let tokens = User::list_o_auth_access_tokens(&test_clerk(server.url()), "user_1", "oauth_google", Some(1), Some(1))
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(tokens.total_count, 2);
+		assert_eq!(tokens.data[0].token.as_deref(), Some("ya29.a0"));
+	}
+
+	#[tokio::test]
+	async fn test_users_get_organization_invitations() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/users/user_1/organization_invitations")
+			.match_query(Matcher::UrlEncoded("status".into(), "pending".into()))
+			.with_body(
+				r#"{"data":[{"id":"orginv_1","object":"organization_invitation","organization_id":"org_1","status":"pending"}],"total_count":1}"#,
+			)
+			.create_async()
+			.await;
+
+		let invitations = User::users_get_organization_invitations(&test_clerk(server.url()), "user_1", None, None, Some("pending"))
+			.await
+			.unwrap();
+
+		mock.assert_async().await;
+		assert_eq!(invitations.data[0].organization_id.as_deref(), Some("org_1"));
+	}
+}
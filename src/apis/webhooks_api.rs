@@ -11,7 +11,7 @@
 use reqwest;
 
 use super::Error;
-use crate::{apis::ResponseContent, clerk::Clerk};
+use crate::{apis::ResponseContent, clerk::Clerk, webhooks::event::WebhookEvent};
 
 /// struct for typed errors of method [`create_svix_app`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +40,14 @@ pub enum GenerateSvixAuthUrlError {
 pub struct Webhooks;
 
 impl Webhooks {
+	/// List the event types Clerk sends webhooks for
+	///
+	/// These are the types [`WebhookEvent`] has a variant for. The endpoints subscribed to them are managed in the Svix
+	/// dashboard, see [`generate_svix_auth_url`](Self::generate_svix_auth_url).
+	pub fn list_event_types() -> &'static [&'static str] {
+		WebhookEvent::EVENT_TYPES
+	}
+
 	/// Create a Svix app and associate it with the current instance
 	pub async fn create_svix_app(clerk_client: &Clerk) -> Result<crate::models::SvixUrl, Error<CreateSvixAppError>> {
 		let local_var_configuration = &clerk_client.config;
@@ -136,3 +144,103 @@ impl Webhooks {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::apis::{test_clerk, Error};
+
+	const ERROR_BODY: &str = r#"{"errors":[{"message":"svix app already exists","long_message":"A Svix app is already associated with this instance","code":"svix_app_exists"}]}"#;
+
+	fn error_code<T>(error: Error<T>, entity: impl Fn(T) -> Option<crate::models::ClerkErrors>) -> Option<String> {
+		match error {
+			Error::ResponseError(content) => content.entity.and_then(entity).map(|errors| errors.errors[0].code.clone()),
+			_ => None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_create_svix_app() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("POST", "/v1/webhooks/svix")
+			.with_body(r#"{"svix_url":"https://app.svix.com/login#key=abc"}"#)
+			.create_async()
+			.await;
+
+		let svix_url = Webhooks::create_svix_app(&test_clerk(server.url())).await.unwrap();
+		assert_eq!(svix_url.svix_url, "https://app.svix.com/login#key=abc");
+	}
+
+	#[tokio::test]
+	async fn test_create_svix_app_error() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("POST", "/v1/webhooks/svix")
+			.with_status(400)
+			.with_body(ERROR_BODY)
+			.create_async()
+			.await;
+
+		let error = Webhooks::create_svix_app(&test_clerk(server.url())).await.unwrap_err();
+		let code = error_code(error, |entity| match entity {
+			CreateSvixAppError::Status400(errors) => Some(errors),
+			CreateSvixAppError::UnknownValue(_) => None,
+		});
+		assert_eq!(code.as_deref(), Some("svix_app_exists"));
+	}
+
+	#[tokio::test]
+	async fn test_delete_svix_app_error() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("DELETE", "/v1/webhooks/svix")
+			.with_status(400)
+			.with_body(ERROR_BODY)
+			.create_async()
+			.await;
+
+		let error = Webhooks::delete_svix_app(&test_clerk(server.url())).await.unwrap_err();
+		let code = error_code(error, |entity| match entity {
+			DeleteSvixAppError::Status400(errors) => Some(errors),
+			DeleteSvixAppError::UnknownValue(_) => None,
+		});
+		assert_eq!(code.as_deref(), Some("svix_app_exists"));
+	}
+
+	#[tokio::test]
+	async fn test_generate_svix_auth_url_error() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("POST", "/v1/webhooks/svix_url")
+			.with_status(404)
+			.with_body("not found")
+			.create_async()
+			.await;
+
+		match Webhooks::generate_svix_auth_url(&test_clerk(server.url())).await {
+			Err(Error::ResponseError(content)) => {
+				assert_eq!(content.status, reqwest::StatusCode::NOT_FOUND);
+				assert!(content.entity.is_none());
+			}
+			res => panic!("expected a response error, got {res:?}"),
+		}
+	}
+
+	#[test]
+	fn test_list_event_types_covers_typed_events() {
+		let event_types = Webhooks::list_event_types();
+		assert_eq!(event_types, WebhookEvent::EVENT_TYPES);
+
+		for event_type in event_types {
+			// a type without a variant would be parsed as unknown, with any data
+			let event = serde_json::json!({ "type": event_type, "data": {} });
+			let parsed = <WebhookEvent as serde::Deserialize>::deserialize(&event);
+			assert!(!matches!(parsed, Ok(WebhookEvent::Unknown(_))), "{event_type} has no variant");
+		}
+
+		for (event_type, _) in crate::webhooks::testing::FIXTURES {
+			assert!(event_types.contains(event_type), "{event_type} is missing");
+		}
+	}
+}
@@ -0,0 +1,147 @@
+//! Signs webhook payloads like Svix does, to test webhook handlers locally.
+//!
+//! ```text
+//! clerk-webhook sign --secret whsec_... payload.json
+//! clerk-webhook send --secret whsec_... --url http://localhost:3000/webhooks/clerk user.created session.ended
+//! clerk-webhook fixtures
+//! ```
+
+use clerk_rs::webhooks::testing::{fixture, SignedWebhook, WebhookSigner, FIXTURES};
+use std::{
+	env,
+	error::Error,
+	fs,
+	io::{self, Read},
+	process::ExitCode,
+};
+
+const USAGE: &str = "Usage:
+  clerk-webhook sign [--secret <whsec_...>] [--id <msg_id>] [--timestamp <unix>] <payload>
+  clerk-webhook send [--secret <whsec_...>] --url <url> [--id <msg_id>] [--timestamp <unix>] <payload>...
+  clerk-webhook fixtures
+
+A payload is a captured event type listed by `fixtures` (e.g. user.created), a JSON file, or - to read stdin.
+The secret defaults to the CLERK_WEBHOOK_SECRET environment variable.";
+
+#[derive(Default)]
+struct Args {
+	command: String,
+	secret: Option<String>,
+	url: Option<String>,
+	id: Option<String>,
+	timestamp: Option<u64>,
+	payloads: Vec<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+	let mut args = env::args().skip(1);
+	let mut parsed = Args {
+		command: args.next().ok_or("missing command")?,
+		..Args::default()
+	};
+
+	while let Some(arg) = args.next() {
+		let mut value = |name: &str| args.next().ok_or(format!("missing value for {}", name));
+		match arg.as_str() {
+			"--secret" => parsed.secret = Some(value("--secret")?),
+			"--url" => parsed.url = Some(value("--url")?),
+			"--id" => parsed.id = Some(value("--id")?),
+			"--timestamp" => parsed.timestamp = Some(value("--timestamp")?.parse().map_err(|_| "invalid --timestamp")?),
+			flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+			_ => parsed.payloads.push(arg),
+		}
+	}
+
+	Ok(parsed)
+}
+
+fn read_payload(payload: &str) -> io::Result<Vec<u8>> {
+	if let Some(fixture) = fixture(payload) {
+		return Ok(fixture.as_bytes().to_vec());
+	}
+
+	match payload {
+		"-" => {
+			let mut buf = Vec::new();
+			io::stdin().read_to_end(&mut buf)?;
+			Ok(buf)
+		}
+		path => fs::read(path),
+	}
+}
+
+fn sign(args: &Args, payload: &str) -> Result<SignedWebhook, Box<dyn Error>> {
+	let secret = match &args.secret {
+		Some(secret) => secret.clone(),
+		None => env::var("CLERK_WEBHOOK_SECRET").map_err(|_| "missing --secret or CLERK_WEBHOOK_SECRET")?,
+	};
+	let signer = WebhookSigner::new(&secret)?;
+	let payload = read_payload(payload).map_err(|error| format!("could not read {}: {}", payload, error))?;
+
+	let webhook = signer.sign(payload);
+	Ok(match (&args.id, args.timestamp) {
+		(None, None) => webhook,
+		(id, timestamp) => signer.sign_at(
+			id.as_deref().unwrap_or(&webhook.id),
+			timestamp.unwrap_or(webhook.timestamp),
+			webhook.payload,
+		),
+	})
+}
+
+async fn run(args: Args) -> Result<(), Box<dyn Error>> {
+	match args.command.as_str() {
+		"fixtures" => {
+			for (event_type, _) in FIXTURES {
+				println!("{}", event_type);
+			}
+		}
+		"sign" => {
+			let [payload] = args.payloads.as_slice() else {
+				return Err("sign takes a single payload".into());
+			};
+
+			for (name, value) in sign(&args, payload)?.headers() {
+				println!("{}: {}", name, value);
+			}
+		}
+		"send" => {
+			let url = args.url.as_deref().ok_or("missing --url")?;
+			if args.payloads.is_empty() {
+				return Err("missing payload".into());
+			}
+
+			let client = reqwest::Client::new();
+			for payload in &args.payloads {
+				let webhook = sign(&args, payload)?;
+				let response = webhook.send(&client, url).await?;
+				println!("{} {}: {}", payload, webhook.id, response.status());
+			}
+		}
+		command => return Err(format!("unknown command {}", command).into()),
+	}
+
+	Ok(())
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args() {
+		Ok(args) => args,
+		Err(error) => {
+			eprintln!("error: {}\n\n{}", error, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	let runtime = tokio::runtime::Builder::new_current_thread()
+		.enable_all()
+		.build()
+		.expect("Failed to start the tokio runtime");
+	match runtime.block_on(run(args)) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(error) => {
+			eprintln!("error: {}", error);
+			ExitCode::FAILURE
+		}
+	}
+}
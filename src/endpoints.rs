@@ -25,8 +25,13 @@ pub enum ClerkDynamicGetEndpoint {
 	GetJwks,
 	GetJwtTemplate,
 	ListPendingOrganizationInvitations,
+	ListOrganizationInvitations,
+	GetOrganizationInvitation,
 	ListOrganizationMemberships,
+	ListOrganizationDomains,
 	GetOrganization,
+	GetOrganizationRole,
+	GetOrganizationPermission,
 	GetPhoneNumber,
 	GetRedirectUrl,
 	GetSession,
@@ -34,6 +39,7 @@ pub enum ClerkDynamicGetEndpoint {
 	GetSignUp,
 	GetUser,
 	UsersGetOrganizationMemberships,
+	UsersGetOrganizationInvitations,
 }
 
 #[derive(Debug)]
@@ -45,14 +51,19 @@ pub enum ClerkDynamicPostEndpoint {
 	RevertTemplate,
 	RevokeInvitation,
 	CreateOrganizationInvitation,
+	CreateOrganizationInvitationBulk,
 	RevokeOrganizationInvitation,
 	CreateOrganizationMembership,
+	AssignPermissionToOrganizationRole,
+	CreateOrganizationDomain,
 	CreateSessionTokenFromTemplate,
 	RevokeSession,
 	VerifySession,
 	RevokeSignInToken,
 	BanUser,
 	UnbanUser,
+	LockUser,
+	UnlockUser,
 	VerifyPassword,
 	VerifyTotp,
 }
@@ -64,11 +75,20 @@ pub enum ClerkDynamicDeleteEndpoint {
 	DeleteEmailAddress,
 	DeleteJwtTemplate,
 	DeleteOrganizationMembership,
+	DeleteOrganizationDomain,
 	DeleteOrganization,
+	DeleteOrganizationRole,
+	DeleteOrganizationPermission,
+	RemovePermissionFromOrganizationRole,
 	DeletePhoneNumber,
 	DeleteRedirectUrl,
 	DeleteUser,
 	DisableMfa,
+	DeleteTotp,
+	DeleteBackupCode,
+	DeletePasskey,
+	DeleteExternalAccount,
+	DeleteWeb3Wallet,
 }
 
 #[derive(Debug)]
@@ -86,7 +106,10 @@ pub enum ClerkDynamicPatchEndpoint {
 	UpdateOrganizationMembershipMetadata,
 	MergeOrganizationMetadata,
 	UpdateOrganizationMembership,
+	UpdateOrganizationDomain,
 	UpdateOrganization,
+	UpdateOrganizationRole,
+	UpdateOrganizationPermission,
 	UpdatePhoneNumber,
 	UpdateSignUp,
 	UpdateUser,
@@ -103,6 +126,8 @@ pub enum ClerkGetEndpoint {
 	ListJwtTemplates,
 	GetPublicInterstitial,
 	ListOrganizations,
+	ListOrganizationRoles,
+	ListOrganizationPermissions,
 	ListRedirectUrls,
 	GetSessionList,
 	GetUserList,
@@ -119,9 +144,12 @@ pub enum ClerkPostEndpoint {
 	CreateEmailAddress,
 	CreateEmail,
 	CreateInvitation,
+	CreateInvitationBulk,
 	CreateJwtTemplate,
 	CreateDemoInstance,
 	CreateOrganization,
+	CreateOrganizationRole,
+	CreateOrganizationPermission,
 	CreatePhoneNumber,
 	CreateRedirectUrl,
 	CreateSignInToken,
@@ -162,6 +190,8 @@ impl ClerkGetEndpoint {
 			ClerkGetEndpoint::ListJwtTemplates => "/jwt_templates",
 			ClerkGetEndpoint::GetPublicInterstitial => "/public/interstitial",
 			ClerkGetEndpoint::ListOrganizations => "/organizations",
+			ClerkGetEndpoint::ListOrganizationRoles => "/organization_roles",
+			ClerkGetEndpoint::ListOrganizationPermissions => "/organization_permissions",
 			ClerkGetEndpoint::ListRedirectUrls => "/redirect_urls",
 			ClerkGetEndpoint::GetSessionList => "/sessions",
 			ClerkGetEndpoint::GetUserList => "/users",
@@ -181,9 +211,12 @@ impl ClerkPostEndpoint {
 			ClerkPostEndpoint::CreateEmailAddress => "/email_addresses",
 			ClerkPostEndpoint::CreateEmail => "/emails",
 			ClerkPostEndpoint::CreateInvitation => "/invitations",
+			ClerkPostEndpoint::CreateInvitationBulk => "/invitations/bulk",
 			ClerkPostEndpoint::CreateJwtTemplate => "/jwt_templates",
 			ClerkPostEndpoint::CreateDemoInstance => "/public/demo_instance",
 			ClerkPostEndpoint::CreateOrganization => "/organizations",
+			ClerkPostEndpoint::CreateOrganizationRole => "/organization_roles",
+			ClerkPostEndpoint::CreateOrganizationPermission => "/organization_permissions",
 			ClerkPostEndpoint::CreatePhoneNumber => "/phone_numbers",
 			ClerkPostEndpoint::CreateRedirectUrl => "/redirect_urls",
 			ClerkPostEndpoint::CreateSignInToken => "/sign_in_tokens",
@@ -229,6 +262,8 @@ impl ClerkDynamicGetEndpoint {
 	pub fn as_str(&self) -> &str {
 		match self {
 			ClerkDynamicGetEndpoint::GetOrganization => "/organizations/{organization_id}",
+			ClerkDynamicGetEndpoint::GetOrganizationRole => "/organization_roles/{organization_role_id}",
+			ClerkDynamicGetEndpoint::GetOrganizationPermission => "/organization_permissions/{organization_permission_id}",
 			ClerkDynamicGetEndpoint::GetPhoneNumber => "/phone_numbers/{phone_number_id}",
 			ClerkDynamicGetEndpoint::GetRedirectUrl => "/redirect_urls/{id}",
 			ClerkDynamicGetEndpoint::GetSession => "/sessions/{session_id}",
@@ -237,7 +272,10 @@ impl ClerkDynamicGetEndpoint {
 			ClerkDynamicGetEndpoint::GetClient => "/clients/{client_id}",
 			ClerkDynamicGetEndpoint::GetJwks => "/jwks",
 			ClerkDynamicGetEndpoint::ListOrganizationMemberships => "/organizations/{organization_id}/memberships",
+			ClerkDynamicGetEndpoint::ListOrganizationDomains => "/organizations/{organization_id}/domains",
 			ClerkDynamicGetEndpoint::ListPendingOrganizationInvitations => "/organizations/{organization_id}/invitations/pending",
+			ClerkDynamicGetEndpoint::ListOrganizationInvitations => "/organizations/{organization_id}/invitations",
+			ClerkDynamicGetEndpoint::GetOrganizationInvitation => "/organizations/{organization_id}/invitations/{invitation_id}",
 			ClerkDynamicGetEndpoint::GetClientLastActiveSession => "/clients/{client_id}/last_active_session",
 			ClerkDynamicGetEndpoint::GetEmailAddress => "/email_addresses/{email_address_id}",
 			ClerkDynamicGetEndpoint::GetTemplate => "/templates/{template_type}/{slug}",
@@ -245,6 +283,7 @@ impl ClerkDynamicGetEndpoint {
 			ClerkDynamicGetEndpoint::GetJwtTemplate => "/jwt_templates/{jwt_template_id}",
 			ClerkDynamicGetEndpoint::GetOAuthAccessToken => "/users/{user_id}/oauth_access_tokens/{provider}",
 			ClerkDynamicGetEndpoint::UsersGetOrganizationMemberships => "/users/{user_id}/organization_memberships",
+			ClerkDynamicGetEndpoint::UsersGetOrganizationInvitations => "/users/{user_id}/organization_invitations",
 		}
 	}
 }
@@ -254,7 +293,10 @@ impl ClerkDynamicPostEndpoint {
 	pub fn as_str(&self) -> &str {
 		match self {
 			ClerkDynamicPostEndpoint::CreateOrganizationMembership => "/organizations/{organization_id}/memberships",
+			ClerkDynamicPostEndpoint::AssignPermissionToOrganizationRole => "/organization_roles/{organization_role_id}/permissions/{permission_id}",
 			ClerkDynamicPostEndpoint::CreateOrganizationInvitation => "/organizations/{organization_id}/invitations",
+			ClerkDynamicPostEndpoint::CreateOrganizationInvitationBulk => "/organizations/{organization_id}/invitations/bulk",
+			ClerkDynamicPostEndpoint::CreateOrganizationDomain => "/organizations/{organization_id}/domains",
 			ClerkDynamicPostEndpoint::RevokeActorToken => "/actor_tokens/{actor_token_id}/revoke",
 			ClerkDynamicPostEndpoint::DeleteBlocklistIdentifier => "/blocklist_identifiers/{identifier_id}",
 			ClerkDynamicPostEndpoint::PreviewTemplate => "/templates/{template_type}/{slug}/preview",
@@ -267,6 +309,8 @@ impl ClerkDynamicPostEndpoint {
 			ClerkDynamicPostEndpoint::RevokeSignInToken => "/sign_in_tokens/{sign_in_token_id}/revoke",
 			ClerkDynamicPostEndpoint::BanUser => "/users/{user_id}/ban",
 			ClerkDynamicPostEndpoint::UnbanUser => "/users/{user_id}/unban",
+			ClerkDynamicPostEndpoint::LockUser => "/users/{user_id}/lock",
+			ClerkDynamicPostEndpoint::UnlockUser => "/users/{user_id}/unlock",
 			ClerkDynamicPostEndpoint::VerifyPassword => "/users/{user_id}/verify_password",
 			ClerkDynamicPostEndpoint::VerifyTotp => "/users/{user_id}/verify_totp",
 		}
@@ -278,7 +322,13 @@ impl ClerkDynamicDeleteEndpoint {
 	pub fn as_str(&self) -> &str {
 		match self {
 			ClerkDynamicDeleteEndpoint::DeleteOrganization => "/organizations/{organization_id}",
+			ClerkDynamicDeleteEndpoint::DeleteOrganizationRole => "/organization_roles/{organization_role_id}",
+			ClerkDynamicDeleteEndpoint::DeleteOrganizationPermission => "/organization_permissions/{organization_permission_id}",
+			ClerkDynamicDeleteEndpoint::RemovePermissionFromOrganizationRole => {
+				"/organization_roles/{organization_role_id}/permissions/{permission_id}"
+			}
 			ClerkDynamicDeleteEndpoint::DeleteOrganizationMembership => "/organizations/{organization_id}/memberships/{user_id}",
+			ClerkDynamicDeleteEndpoint::DeleteOrganizationDomain => "/organizations/{organization_id}/domains/{domain_id}",
 			ClerkDynamicDeleteEndpoint::DeletePhoneNumber => "/phone_numbers/{phone_number_id}",
 			ClerkDynamicDeleteEndpoint::DeleteRedirectUrl => "/redirect_urls/{id}",
 			ClerkDynamicDeleteEndpoint::DeleteUser => "/users/{user_id}",
@@ -286,6 +336,11 @@ impl ClerkDynamicDeleteEndpoint {
 			ClerkDynamicDeleteEndpoint::DeleteJwtTemplate => "/jwt_templates/{jwt_template_id}",
 			ClerkDynamicDeleteEndpoint::DeleteAllowlistIdentifier => "/allowlist_identifiers/{identifier_id}",
 			ClerkDynamicDeleteEndpoint::DisableMfa => "/users/{user_id}/disable_mfa",
+			ClerkDynamicDeleteEndpoint::DeleteTotp => "/users/{user_id}/totp",
+			ClerkDynamicDeleteEndpoint::DeleteBackupCode => "/users/{user_id}/backup_code",
+			ClerkDynamicDeleteEndpoint::DeletePasskey => "/users/{user_id}/passkeys/{passkey_identification_id}",
+			ClerkDynamicDeleteEndpoint::DeleteExternalAccount => "/users/{user_id}/external_accounts/{external_account_id}",
+			ClerkDynamicDeleteEndpoint::DeleteWeb3Wallet => "/users/{user_id}/web3_wallets/{web3_wallet_identification_id}",
 		}
 	}
 }
@@ -305,7 +360,10 @@ impl ClerkDynamicPatchEndpoint {
 	pub fn as_str(&self) -> &str {
 		match self {
 			ClerkDynamicPatchEndpoint::UpdateOrganization => "/organizations/{organization_id}",
+			ClerkDynamicPatchEndpoint::UpdateOrganizationRole => "/organization_roles/{organization_role_id}",
+			ClerkDynamicPatchEndpoint::UpdateOrganizationPermission => "/organization_permissions/{organization_permission_id}",
 			ClerkDynamicPatchEndpoint::UpdateOrganizationMembership => "/organizations/{organization_id}/memberships/{user_id}",
+			ClerkDynamicPatchEndpoint::UpdateOrganizationDomain => "/organizations/{organization_id}/domains/{domain_id}",
 			ClerkDynamicPatchEndpoint::UpdatePhoneNumber => "/phone_numbers/{phone_number_id}",
 			ClerkDynamicPatchEndpoint::UpdateUser => "/users/{user_id}",
 			ClerkDynamicPatchEndpoint::UpdateEmailAddress => "/email_addresses/{email_address_id}",
@@ -12,5 +12,6 @@ pub mod endpoints;
 pub mod models;
 pub(crate) mod util;
 pub mod validators;
+pub mod webhooks;
 pub use apis::configuration::ClerkConfiguration;
 pub use models as ClerkModels;
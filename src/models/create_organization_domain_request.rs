@@ -0,0 +1,32 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateOrganizationDomainRequest {
+	/// The name of the new domain, e.g. `example.com`
+	#[serde(rename = "name")]
+	pub name: String,
+	/// The enrollment mode of the new domain. Defaults to `manual_invitation`.
+	#[serde(rename = "enrollment_mode", skip_serializing_if = "Option::is_none")]
+	pub enrollment_mode: Option<crate::models::organization_domain::EnrollmentMode>,
+	/// Whether the domain is created already verified. Defaults to `true`.
+	#[serde(rename = "verified", skip_serializing_if = "Option::is_none")]
+	pub verified: Option<bool>,
+}
+
+impl CreateOrganizationDomainRequest {
+	pub fn new(name: String) -> CreateOrganizationDomainRequest {
+		CreateOrganizationDomainRequest {
+			name,
+			enrollment_mode: None,
+			verified: None,
+		}
+	}
+}
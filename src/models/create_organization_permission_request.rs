@@ -0,0 +1,30 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateOrganizationPermissionRequest {
+	/// The name of the new permission
+	#[serde(rename = "name")]
+	pub name: String,
+	/// The key of the new permission, in the `org:<feature>:<action>` format
+	#[serde(rename = "key")]
+	pub key: String,
+	#[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+}
+
+impl CreateOrganizationPermissionRequest {
+	pub fn new(name: String, key: String) -> CreateOrganizationPermissionRequest {
+		CreateOrganizationPermissionRequest {
+			name,
+			key,
+			description: None,
+		}
+	}
+}
@@ -0,0 +1,34 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateOrganizationRoleRequest {
+	/// The name of the new role
+	#[serde(rename = "name")]
+	pub name: String,
+	/// The key of the new role. Must start with `org:` and be unique for the instance.
+	#[serde(rename = "key")]
+	pub key: String,
+	#[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// The IDs of the permissions the role grants
+	#[serde(rename = "permissions", skip_serializing_if = "Option::is_none")]
+	pub permissions: Option<Vec<String>>,
+}
+
+impl CreateOrganizationRoleRequest {
+	pub fn new(name: String, key: String) -> CreateOrganizationRoleRequest {
+		CreateOrganizationRoleRequest {
+			name,
+			key,
+			description: None,
+			permissions: None,
+		}
+	}
+}
@@ -0,0 +1,20 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeleteBackupCode200Response {
+	#[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+	pub user_id: Option<String>,
+}
+
+impl DeleteBackupCode200Response {
+	pub fn new() -> DeleteBackupCode200Response {
+		DeleteBackupCode200Response { user_id: None }
+	}
+}
@@ -17,7 +17,7 @@ pub struct EmailAddress {
 	pub object: Object,
 	#[serde(rename = "email_address")]
 	pub email_address: String,
-	#[serde(rename = "reserved")]
+	#[serde(rename = "reserved", default)]
 	pub reserved: bool,
 	#[serde(rename = "verification")]
 	pub verification: Option<Box<crate::models::EmailAddressVerification>>,
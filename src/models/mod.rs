@@ -34,14 +34,20 @@ pub mod create_jwt_template_request;
 pub use self::create_jwt_template_request::CreateJwtTemplateRequest;
 pub mod create_o_auth_application_request;
 pub use self::create_o_auth_application_request::CreateOAuthApplicationRequest;
+pub mod create_organization_domain_request;
+pub use self::create_organization_domain_request::CreateOrganizationDomainRequest;
 pub mod create_organization_invitation_bulk_request_inner;
 pub use self::create_organization_invitation_bulk_request_inner::CreateOrganizationInvitationBulkRequestInner;
 pub mod create_organization_invitation_request;
 pub use self::create_organization_invitation_request::CreateOrganizationInvitationRequest;
 pub mod create_organization_membership_request;
 pub use self::create_organization_membership_request::CreateOrganizationMembershipRequest;
+pub mod create_organization_permission_request;
+pub use self::create_organization_permission_request::CreateOrganizationPermissionRequest;
 pub mod create_organization_request;
 pub use self::create_organization_request::CreateOrganizationRequest;
+pub mod create_organization_role_request;
+pub use self::create_organization_role_request::CreateOrganizationRoleRequest;
 pub mod create_phone_number_request;
 pub use self::create_phone_number_request::CreatePhoneNumberRequest;
 pub mod create_redirect_url_request;
@@ -54,6 +60,10 @@ pub mod create_sign_in_token_request;
 pub use self::create_sign_in_token_request::CreateSignInTokenRequest;
 pub mod create_user_request;
 pub use self::create_user_request::CreateUserRequest;
+pub mod delete_backup_code_200_response;
+pub use self::delete_backup_code_200_response::DeleteBackupCode200Response;
+pub mod delete_totp_200_response;
+pub use self::delete_totp_200_response::DeleteTotp200Response;
 pub mod deleted_object;
 pub use self::deleted_object::DeletedObject;
 pub mod disable_mfa_200_response;
@@ -80,6 +90,8 @@ pub mod jwt_template;
 pub use self::jwt_template::JwtTemplate;
 pub mod merge_organization_metadata_request;
 pub use self::merge_organization_metadata_request::MergeOrganizationMetadataRequest;
+pub mod o_auth_access_tokens;
+pub use self::o_auth_access_tokens::OAuthAccessTokens;
 pub mod o_auth_application;
 pub use self::o_auth_application::OAuthApplication;
 pub mod o_auth_application_with_secret;
@@ -88,6 +100,12 @@ pub mod o_auth_applications;
 pub use self::o_auth_applications::OAuthApplications;
 pub mod organization;
 pub use self::organization::Organization;
+pub mod organization_domain;
+pub use self::organization_domain::OrganizationDomain;
+pub mod organization_domain_verification;
+pub use self::organization_domain_verification::OrganizationDomainVerification;
+pub mod organization_domains;
+pub use self::organization_domains::OrganizationDomains;
 pub mod organization_invitation;
 pub use self::organization_invitation::OrganizationInvitation;
 pub mod organization_invitations;
@@ -98,6 +116,14 @@ pub mod organization_membership_public_user_data;
 pub use self::organization_membership_public_user_data::OrganizationMembershipPublicUserData;
 pub mod organization_memberships;
 pub use self::organization_memberships::OrganizationMemberships;
+pub mod organization_permission;
+pub use self::organization_permission::OrganizationPermission;
+pub mod organization_permissions;
+pub use self::organization_permissions::OrganizationPermissions;
+pub mod organization_role;
+pub use self::organization_role::OrganizationRole;
+pub mod organization_roles;
+pub use self::organization_roles::OrganizationRoles;
 pub mod organization_settings;
 pub use self::organization_settings::OrganizationSettings;
 pub mod organization_with_logo;
@@ -106,6 +132,8 @@ pub mod organizations;
 pub use self::organizations::Organizations;
 pub mod otp;
 pub use self::otp::Otp;
+pub mod passkey;
+pub use self::passkey::Passkey;
 pub mod phone_number;
 pub use self::phone_number::PhoneNumber;
 pub mod preview_template_request;
@@ -160,12 +188,18 @@ pub mod update_instance_restrictions_request;
 pub use self::update_instance_restrictions_request::UpdateInstanceRestrictionsRequest;
 pub mod update_o_auth_application_request;
 pub use self::update_o_auth_application_request::UpdateOAuthApplicationRequest;
+pub mod update_organization_domain_request;
+pub use self::update_organization_domain_request::UpdateOrganizationDomainRequest;
 pub mod update_organization_membership_metadata_request;
 pub use self::update_organization_membership_metadata_request::UpdateOrganizationMembershipMetadataRequest;
 pub mod update_organization_membership_request;
 pub use self::update_organization_membership_request::UpdateOrganizationMembershipRequest;
+pub mod update_organization_permission_request;
+pub use self::update_organization_permission_request::UpdateOrganizationPermissionRequest;
 pub mod update_organization_request;
 pub use self::update_organization_request::UpdateOrganizationRequest;
+pub mod update_organization_role_request;
+pub use self::update_organization_role_request::UpdateOrganizationRoleRequest;
 pub mod update_phone_number_request;
 pub use self::update_phone_number_request::UpdatePhoneNumberRequest;
 pub mod update_production_instance_domain_request;
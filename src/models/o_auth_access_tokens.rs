@@ -0,0 +1,23 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OAuthAccessTokens {
+	#[serde(rename = "data")]
+	pub data: Vec<crate::models::GetOAuthAccessToken200ResponseInner>,
+	/// Total number of OAuth access tokens
+	#[serde(rename = "total_count")]
+	pub total_count: i64,
+}
+
+impl OAuthAccessTokens {
+	pub fn new(data: Vec<crate::models::GetOAuthAccessToken200ResponseInner>, total_count: i64) -> OAuthAccessTokens {
+		OAuthAccessTokens { data, total_count }
+	}
+}
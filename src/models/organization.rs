@@ -20,13 +20,13 @@ pub struct Organization {
 	pub slug: String,
 	#[serde(rename = "members_count", skip_serializing_if = "Option::is_none")]
 	pub members_count: Option<i64>,
-	#[serde(rename = "max_allowed_memberships")]
+	#[serde(rename = "max_allowed_memberships", default)]
 	pub max_allowed_memberships: i64,
 	#[serde(rename = "admin_delete_enabled", skip_serializing_if = "Option::is_none")]
 	pub admin_delete_enabled: Option<bool>,
 	#[serde(rename = "public_metadata")]
 	pub public_metadata: serde_json::Value,
-	#[serde(rename = "private_metadata")]
+	#[serde(rename = "private_metadata", default)]
 	pub private_metadata: serde_json::Value,
 	#[serde(rename = "created_by", skip_serializing_if = "Option::is_none")]
 	pub created_by: Option<String>,
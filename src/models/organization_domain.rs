@@ -0,0 +1,109 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+/// OrganizationDomain : A domain of an organization, whose users can join it depending on its enrollment mode
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationDomain {
+	/// String representing the object's type. Objects of the same type share the same value.
+	#[serde(rename = "object")]
+	pub object: Object,
+	#[serde(rename = "id")]
+	pub id: String,
+	#[serde(rename = "organization_id")]
+	pub organization_id: String,
+	/// The domain name, e.g. `example.com`
+	#[serde(rename = "name")]
+	pub name: String,
+	#[serde(rename = "enrollment_mode")]
+	pub enrollment_mode: EnrollmentMode,
+	/// The email address used to verify the domain
+	#[serde(rename = "affiliation_email_address", default, skip_serializing_if = "Option::is_none")]
+	pub affiliation_email_address: Option<String>,
+	#[serde(rename = "verification", default, skip_serializing_if = "Option::is_none")]
+	pub verification: Option<Box<crate::models::OrganizationDomainVerification>>,
+	#[serde(rename = "total_pending_invitations", default)]
+	pub total_pending_invitations: i64,
+	#[serde(rename = "total_pending_suggestions", default)]
+	pub total_pending_suggestions: i64,
+	/// Unix timestamp of creation.
+	#[serde(rename = "created_at")]
+	pub created_at: i64,
+	/// Unix timestamp of last update.
+	#[serde(rename = "updated_at")]
+	pub updated_at: i64,
+}
+
+impl OrganizationDomain {
+	/// A domain of an organization, whose users can join it depending on its enrollment mode
+	pub fn new(
+		object: Object,
+		id: String,
+		organization_id: String,
+		name: String,
+		enrollment_mode: EnrollmentMode,
+		created_at: i64,
+		updated_at: i64,
+	) -> OrganizationDomain {
+		OrganizationDomain {
+			object,
+			id,
+			organization_id,
+			name,
+			enrollment_mode,
+			affiliation_email_address: None,
+			verification: None,
+			total_pending_invitations: 0,
+			total_pending_suggestions: 0,
+			created_at,
+			updated_at,
+		}
+	}
+
+	/// Whether the domain was verified, which the automatic enrollment modes require.
+	pub fn is_verified(&self) -> bool {
+		self.verification
+			.as_ref()
+			.is_some_and(|verification| verification.status == crate::models::organization_domain_verification::Status::Verified)
+	}
+}
+
+/// String representing the object's type. Objects of the same type share the same value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum Object {
+	#[default]
+	#[serde(rename = "organization_domain")]
+	OrganizationDomain,
+}
+
+/// How users with an email address on the domain join the organization.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum EnrollmentMode {
+	/// Users are only added when invited by an admin.
+	#[default]
+	#[serde(rename = "manual_invitation")]
+	ManualInvitation,
+	/// Users are invited to the organization when they sign up, and join it once they accept.
+	#[serde(rename = "automatic_invitation")]
+	AutomaticInvitation,
+	/// Users are suggested to request to join the organization, which an admin then approves.
+	#[serde(rename = "automatic_suggestion")]
+	AutomaticSuggestion,
+}
+
+impl EnrollmentMode {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			EnrollmentMode::ManualInvitation => "manual_invitation",
+			EnrollmentMode::AutomaticInvitation => "automatic_invitation",
+			EnrollmentMode::AutomaticSuggestion => "automatic_suggestion",
+		}
+	}
+}
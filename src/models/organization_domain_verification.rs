@@ -0,0 +1,43 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationDomainVerification {
+	#[serde(rename = "status")]
+	pub status: Status,
+	/// The strategy used to verify the domain, e.g. `email_code`
+	#[serde(rename = "strategy")]
+	pub strategy: String,
+	#[serde(rename = "attempts", default)]
+	pub attempts: Option<i64>,
+	/// Unix timestamp of when the verification expires.
+	#[serde(rename = "expire_at", default)]
+	pub expire_at: Option<i64>,
+}
+
+impl OrganizationDomainVerification {
+	pub fn new(status: Status, strategy: String) -> OrganizationDomainVerification {
+		OrganizationDomainVerification {
+			status,
+			strategy,
+			attempts: None,
+			expire_at: None,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum Status {
+	#[default]
+	#[serde(rename = "unverified")]
+	Unverified,
+	#[serde(rename = "verified")]
+	Verified,
+}
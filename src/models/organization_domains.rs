@@ -0,0 +1,24 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationDomains {
+	#[serde(rename = "data")]
+	pub data: Vec<crate::models::OrganizationDomain>,
+	/// Total number of organization domains
+	#[serde(rename = "total_count")]
+	pub total_count: i64,
+}
+
+impl OrganizationDomains {
+	pub fn new(data: Vec<crate::models::OrganizationDomain>, total_count: i64) -> OrganizationDomains {
+		OrganizationDomains { data, total_count }
+	}
+}
@@ -0,0 +1,68 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+/// OrganizationPermission : A permission organization roles can grant
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationPermission {
+	/// String representing the object's type. Objects of the same type share the same value.
+	#[serde(rename = "object")]
+	pub object: Object,
+	#[serde(rename = "id")]
+	pub id: String,
+	#[serde(rename = "name")]
+	pub name: String,
+	/// The key of the permission, e.g. `org:invoices:read`, which is what session tokens carry
+	#[serde(rename = "key")]
+	pub key: String,
+	#[serde(rename = "description", default)]
+	pub description: Option<String>,
+	#[serde(rename = "type")]
+	pub r#type: Type,
+	/// Unix timestamp of creation.
+	#[serde(rename = "created_at")]
+	pub created_at: i64,
+	/// Unix timestamp of last update.
+	#[serde(rename = "updated_at")]
+	pub updated_at: i64,
+}
+
+impl OrganizationPermission {
+	/// A permission organization roles can grant
+	pub fn new(object: Object, id: String, name: String, key: String, r#type: Type, created_at: i64, updated_at: i64) -> OrganizationPermission {
+		OrganizationPermission {
+			object,
+			id,
+			name,
+			key,
+			description: None,
+			r#type,
+			created_at,
+			updated_at,
+		}
+	}
+}
+
+/// String representing the object's type. Objects of the same type share the same value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum Object {
+	#[default]
+	#[serde(rename = "permission")]
+	Permission,
+}
+
+/// Whether the permission is one of the `org:sys_*` permissions of Clerk, or a custom one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum Type {
+	#[serde(rename = "system")]
+	System,
+	#[default]
+	#[serde(rename = "user")]
+	User,
+}
@@ -0,0 +1,62 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+/// OrganizationRole : A role members of an organization can have, along with the permissions it grants
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationRole {
+	/// String representing the object's type. Objects of the same type share the same value.
+	#[serde(rename = "object")]
+	pub object: Object,
+	#[serde(rename = "id")]
+	pub id: String,
+	#[serde(rename = "name")]
+	pub name: String,
+	/// The key of the role, e.g. `org:admin`, which is what session tokens carry
+	#[serde(rename = "key")]
+	pub key: String,
+	#[serde(rename = "description", default)]
+	pub description: Option<String>,
+	/// Whether the role can be given to the creator of an organization
+	#[serde(rename = "is_creator_eligible", default)]
+	pub is_creator_eligible: bool,
+	#[serde(rename = "permissions", default)]
+	pub permissions: Vec<crate::models::OrganizationPermission>,
+	/// Unix timestamp of creation.
+	#[serde(rename = "created_at")]
+	pub created_at: i64,
+	/// Unix timestamp of last update.
+	#[serde(rename = "updated_at")]
+	pub updated_at: i64,
+}
+
+impl OrganizationRole {
+	/// A role members of an organization can have, along with the permissions it grants
+	pub fn new(object: Object, id: String, name: String, key: String, created_at: i64, updated_at: i64) -> OrganizationRole {
+		OrganizationRole {
+			object,
+			id,
+			name,
+			key,
+			description: None,
+			is_creator_eligible: false,
+			permissions: Vec::new(),
+			created_at,
+			updated_at,
+		}
+	}
+}
+
+/// String representing the object's type. Objects of the same type share the same value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum Object {
+	#[default]
+	#[serde(rename = "role")]
+	Role,
+}
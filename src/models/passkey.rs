@@ -0,0 +1,45 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Passkey {
+	#[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+	pub id: Option<String>,
+	/// String representing the object's type. Objects of the same type share the same value.
+	#[serde(rename = "object")]
+	pub object: Object,
+	/// The name the user gave the passkey
+	#[serde(rename = "name")]
+	pub name: String,
+	/// Unix timestamp of when the passkey was last used to sign in.
+	#[serde(rename = "last_used_at", default)]
+	pub last_used_at: Option<i64>,
+	#[serde(rename = "verification", default)]
+	pub verification: Option<serde_json::Value>,
+}
+
+impl Passkey {
+	pub fn new(object: Object, name: String) -> Passkey {
+		Passkey {
+			id: None,
+			object,
+			name,
+			last_used_at: None,
+			verification: None,
+		}
+	}
+}
+
+/// String representing the object's type. Objects of the same type share the same value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default)]
+pub enum Object {
+	#[default]
+	#[serde(rename = "passkey")]
+	Passkey,
+}
@@ -21,7 +21,7 @@ pub struct PhoneNumber {
 	pub reserved_for_second_factor: Option<bool>,
 	#[serde(rename = "default_second_factor", skip_serializing_if = "Option::is_none")]
 	pub default_second_factor: Option<bool>,
-	#[serde(rename = "reserved")]
+	#[serde(rename = "reserved", default)]
 	pub reserved: bool,
 	#[serde(rename = "verification")]
 	pub verification: Option<Box<crate::models::EmailAddressVerification>>,
@@ -0,0 +1,32 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateOrganizationDomainRequest {
+	/// The new name of the domain
+	#[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// The new enrollment mode of the domain. The automatic modes require a verified domain.
+	#[serde(rename = "enrollment_mode", skip_serializing_if = "Option::is_none")]
+	pub enrollment_mode: Option<crate::models::organization_domain::EnrollmentMode>,
+	/// Whether the domain is verified
+	#[serde(rename = "verified", skip_serializing_if = "Option::is_none")]
+	pub verified: Option<bool>,
+}
+
+impl UpdateOrganizationDomainRequest {
+	pub fn new() -> UpdateOrganizationDomainRequest {
+		UpdateOrganizationDomainRequest {
+			name: None,
+			enrollment_mode: None,
+			verified: None,
+		}
+	}
+}
@@ -0,0 +1,30 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateOrganizationPermissionRequest {
+	/// The new name of the permission
+	#[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// The new key of the permission, in the `org:<feature>:<action>` format
+	#[serde(rename = "key", skip_serializing_if = "Option::is_none")]
+	pub key: Option<String>,
+	#[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+}
+
+impl UpdateOrganizationPermissionRequest {
+	pub fn new() -> UpdateOrganizationPermissionRequest {
+		UpdateOrganizationPermissionRequest {
+			name: None,
+			key: None,
+			description: None,
+		}
+	}
+}
@@ -0,0 +1,34 @@
+/*
+ * Clerk Backend API
+ *
+ * The Clerk REST Backend API, meant to be accessed by backend servers. Please see https://clerk.com/docs for more information.
+ *
+ * The version of the OpenAPI document: v1
+ * Contact: support@clerk.com
+ * Generated by: https://openapi-generator.tech
+ */
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateOrganizationRoleRequest {
+	/// The new name of the role
+	#[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// The new key of the role. Must start with `org:` and be unique for the instance.
+	#[serde(rename = "key", skip_serializing_if = "Option::is_none")]
+	pub key: Option<String>,
+	#[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// The IDs of the permissions the role grants, replacing its current permissions
+	#[serde(rename = "permissions", skip_serializing_if = "Option::is_none")]
+	pub permissions: Option<Vec<String>>,
+}
+
+impl UpdateOrganizationRoleRequest {
+	pub fn new() -> UpdateOrganizationRoleRequest {
+		UpdateOrganizationRoleRequest {
+			name: None,
+			key: None,
+			description: None,
+			permissions: None,
+		}
+	}
+}
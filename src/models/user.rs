@@ -51,6 +51,8 @@ pub struct User {
 	pub phone_numbers: Option<Vec<crate::models::PhoneNumber>>,
 	#[serde(rename = "web3_wallets", skip_serializing_if = "Option::is_none")]
 	pub web3_wallets: Option<Vec<crate::models::Web3Wallet>>,
+	#[serde(rename = "passkeys", skip_serializing_if = "Option::is_none")]
+	pub passkeys: Option<Vec<crate::models::Passkey>>,
 	#[serde(rename = "password_enabled", skip_serializing_if = "Option::is_none")]
 	pub password_enabled: Option<bool>,
 	#[serde(rename = "two_factor_enabled", skip_serializing_if = "Option::is_none")]
@@ -118,6 +120,7 @@ impl User {
 			email_addresses: None,
 			phone_numbers: None,
 			web3_wallets: None,
+			passkeys: None,
 			password_enabled: None,
 			two_factor_enabled: None,
 			totp_enabled: None,
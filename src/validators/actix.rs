@@ -1,25 +1,30 @@
 use crate::validators::{
-	authorizer::{ClerkAuthorizer, ClerkError, ClerkRequest},
+	authorizer::{AuthCheck, ClerkAuthorizer, ClerkError, ClerkJwt, ClerkRequest},
 	jwks::JwksProvider,
+	request_state::RequestState,
+	session_status::SessionStatusScope,
 };
 use actix_web::{
 	body::EitherBody,
 	dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
 	error::Error,
+	http::{
+		header::{HeaderName, HeaderValue},
+		StatusCode,
+	},
 	HttpMessage, HttpResponse,
 };
 use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
 use std::{
 	future::{ready, Ready},
+	marker::PhantomData,
 	rc::Rc,
 };
 
 impl ClerkRequest for ServiceRequest {
 	fn get_header(&self, key: &str) -> Option<String> {
-		match self.headers().get(key) {
-			Some(header) => Some(header.to_str().expect("header must be a string").to_string()),
-			None => None,
-		}
+		self.headers().get(key).and_then(|header| header.to_str().ok()).map(str::to_string)
 	}
 
 	fn get_cookie(&self, key: &str) -> Option<String> {
@@ -28,6 +33,14 @@ impl ClerkRequest for ServiceRequest {
 			None => None,
 		}
 	}
+
+	fn get_uri(&self) -> Option<String> {
+		self.uri().path_and_query().map(|val| val.to_string())
+	}
+
+	fn get_method(&self) -> Option<String> {
+		Some(self.method().to_string())
+	}
 }
 
 /// Actix-web middleware for protecting a http endpoint with Clerk.dev.
@@ -53,29 +66,51 @@ impl ClerkRequest for ServiceRequest {
 ///     .await
 /// }
 /// ```
-pub struct ClerkMiddleware<J> {
-	pub authorizer: ClerkAuthorizer<J>,
+///
+/// The verified [`ClerkJwt<C>`](crate::validators::authorizer::ClerkJwt) is added to the request extensions, where `C`
+/// are the custom claims of the authorizer, see [`ClerkAuthorizer::with_claims`].
+pub struct ClerkMiddleware<J, C = ()> {
+	pub authorizer: ClerkAuthorizer<J, C>,
 	pub routes: Option<Vec<String>>,
+	pub session_status: SessionStatusScope,
 }
 
 impl<J: JwksProvider> ClerkMiddleware<J> {
 	pub fn new(jwks_provider: J, routes: Option<Vec<String>>, validate_session_cookie: bool) -> Self {
 		let authorizer = ClerkAuthorizer::new(jwks_provider, validate_session_cookie);
-		Self { authorizer, routes }
+		Self::from_authorizer(authorizer, routes)
 	}
 }
 
-impl<S: 'static, B, J> Transform<S, ServiceRequest> for ClerkMiddleware<J>
+impl<J: JwksProvider, C> ClerkMiddleware<J, C> {
+	/// Creates the middleware from a configured authorizer, e.g. one using [`ClerkAuthorizer::with_request_state`].
+	pub fn from_authorizer(authorizer: ClerkAuthorizer<J, C>, routes: Option<Vec<String>>) -> Self {
+		Self {
+			authorizer,
+			routes,
+			session_status: SessionStatusScope::default(),
+		}
+	}
+
+	/// Selects the routes that also confirm the session is still active, see [`ClerkAuthorizer::with_session_status`].
+	pub fn with_session_status(mut self, scope: SessionStatusScope) -> Self {
+		self.session_status = scope;
+		self
+	}
+}
+
+impl<S: 'static, B, J, C> Transform<S, ServiceRequest> for ClerkMiddleware<J, C>
 where
 	S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
 	S::Future: 'static,
 	B: 'static,
 	J: JwksProvider + 'static,
+	C: DeserializeOwned + 'static,
 {
 	type Response = ServiceResponse<EitherBody<B>>;
 	type Error = Error;
 	type InitError = ();
-	type Transform = ClerkMiddlewareService<S, J>;
+	type Transform = ClerkMiddlewareService<S, J, C>;
 	type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
 	fn new_transform(&self, service: S) -> Self::Future {
@@ -83,22 +118,25 @@ where
 			service: Rc::new(service),
 			authorizer: self.authorizer.clone(),
 			routes: self.routes.clone(),
+			session_status: self.session_status.clone(),
 		}))
 	}
 }
 
-pub struct ClerkMiddlewareService<S, J> {
+pub struct ClerkMiddlewareService<S, J, C = ()> {
 	service: Rc<S>,
-	authorizer: ClerkAuthorizer<J>,
+	authorizer: ClerkAuthorizer<J, C>,
 	routes: Option<Vec<String>>,
+	session_status: SessionStatusScope,
 }
 
-impl<S: 'static, B, J> Service<ServiceRequest> for ClerkMiddlewareService<S, J>
+impl<S: 'static, B, J, C> Service<ServiceRequest> for ClerkMiddlewareService<S, J, C>
 where
 	S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
 	S::Future: 'static,
 	B: 'static,
 	J: JwksProvider + 'static,
+	C: DeserializeOwned + 'static,
 {
 	type Response = ServiceResponse<EitherBody<B>>;
 	type Error = Error;
@@ -109,6 +147,7 @@ where
 	fn call(&self, request: ServiceRequest) -> Self::Future {
 		let svc = self.service.clone();
 		let authorizer = self.authorizer.clone();
+		let verify_session_status = self.session_status.applies_to(request.path());
 
 		// We want to skip running the validator if we are not able to find a matching path from the listed valid paths provided by the user
 		match &self.routes {
@@ -132,31 +171,153 @@ where
 
 		Box::pin(async move {
 			// Check if the request is authenticated
-			match authorizer.authorize(&request).await {
+			match authorizer.authenticate_request_with_status(&request, verify_session_status).await {
 				// We have authed request and can pass the user onto the next body
-				Ok(jwt) => {
+				RequestState { jwt: Some(jwt), headers, .. } => {
 					request.extensions_mut().insert(jwt);
-					let res = svc.call(request).await?;
+					let mut res = svc.call(request).await?;
+					// e.g. the cookies refreshed by a handshake
+					for (key, val) in headers {
+						if let (Ok(key), Ok(val)) = (HeaderName::try_from(key), HeaderValue::try_from(val)) {
+							res.headers_mut().append(key, val);
+						}
+					}
 					return Ok(res.map_into_left_body());
 				}
-				// Output any other errors thrown from the Clerk authorizer
-				Err(error) => {
-					match error {
-						ClerkError::Unauthorized(msg) => {
-							return Ok(ServiceResponse::new(
-								request.into_parts().0,
-								HttpResponse::Unauthorized().body(msg).map_into_right_body(),
-							))
-						}
-						ClerkError::InternalServerError(msg) => {
-							return Ok(ServiceResponse::new(
-								request.into_parts().0,
-								HttpResponse::InternalServerError().body(msg).map_into_right_body(),
-							))
-						}
-					};
+				// Output any errors thrown from the Clerk authorizer, or the handshake the browser has to go through
+				state => {
+					let mut response = HttpResponse::build(StatusCode::from_u16(state.status_code()).unwrap_or(StatusCode::UNAUTHORIZED));
+					for header in state.headers.iter().cloned() {
+						response.append_header(header);
+					}
+
+					return Ok(ServiceResponse::new(
+						request.into_parts().0,
+						response.body(state.response_body()).map_into_right_body(),
+					));
 				}
 			}
 		})
 	}
 }
+
+/// Actix-web middleware that only lets requests through whose session passes an [`AuthCheck`], responding with a 403
+/// otherwise.
+///
+/// It reads the jwt added by [`ClerkMiddleware`], so it has to be registered before it (actix runs the middleware
+/// registered last first):
+/// ```
+/// App::new()
+///     .wrap(AuthCheckMiddleware::new(AuthCheck::role("org:admin")))
+///     .wrap(ClerkMiddleware::new(MemoryCacheJwksProvider::new(clerk), None, true))
+///     .route("/admin", web::get().to(admin))
+/// ```
+pub struct AuthCheckMiddleware<C = ()> {
+	check: Rc<AuthCheck>,
+	_claims: PhantomData<fn() -> C>,
+}
+
+impl AuthCheckMiddleware {
+	pub fn new(check: AuthCheck) -> Self {
+		Self {
+			check: Rc::new(check),
+			_claims: PhantomData,
+		}
+	}
+}
+
+impl<C> AuthCheckMiddleware<C> {
+	/// Reads the jwt of a middleware using custom claims, see [`ClerkAuthorizer::with_claims`].
+	pub fn with_claims<T>(self) -> AuthCheckMiddleware<T> {
+		AuthCheckMiddleware {
+			check: self.check,
+			_claims: PhantomData,
+		}
+	}
+}
+
+impl<S: 'static, B, C> Transform<S, ServiceRequest> for AuthCheckMiddleware<C>
+where
+	S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+	S::Future: 'static,
+	B: 'static,
+	C: 'static,
+{
+	type Response = ServiceResponse<EitherBody<B>>;
+	type Error = Error;
+	type InitError = ();
+	type Transform = AuthCheckMiddlewareService<S, C>;
+	type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+	fn new_transform(&self, service: S) -> Self::Future {
+		ready(Ok(AuthCheckMiddlewareService {
+			service: Rc::new(service),
+			check: self.check.clone(),
+			_claims: PhantomData,
+		}))
+	}
+}
+
+pub struct AuthCheckMiddlewareService<S, C = ()> {
+	service: Rc<S>,
+	check: Rc<AuthCheck>,
+	_claims: PhantomData<fn() -> C>,
+}
+
+impl<S: 'static, B, C> Service<ServiceRequest> for AuthCheckMiddlewareService<S, C>
+where
+	S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+	S::Future: 'static,
+	B: 'static,
+	C: 'static,
+{
+	type Response = ServiceResponse<EitherBody<B>>;
+	type Error = Error;
+	type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	forward_ready!(service);
+
+	fn call(&self, request: ServiceRequest) -> Self::Future {
+		let result = match request.extensions().get::<ClerkJwt<C>>() {
+			Some(jwt) => jwt.require(&self.check),
+			None => Err(ClerkError::Unauthorized(String::from("Error: The request is not signed in!"))),
+		};
+
+		match result {
+			Ok(()) => {
+				let svc = self.service.clone();
+				Box::pin(async move {
+					let res = svc.call(request).await?;
+					Ok(res.map_into_left_body())
+				})
+			}
+			Err(error) => {
+				let mut response = HttpResponse::build(StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::FORBIDDEN));
+				if let Some(content_type) = error.content_type() {
+					response.content_type(content_type);
+				}
+				let response = response.body(error.message()).map_into_right_body();
+				Box::pin(ready(Ok(ServiceResponse::new(request.into_parts().0, response))))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use actix_web::test::TestRequest;
+
+	#[test]
+	fn test_service_request_header() {
+		let request = TestRequest::default()
+			.insert_header(("Authorization", "Bearer token"))
+			.insert_header(("Referer", HeaderValue::from_bytes(b"https://example.com/\xff").unwrap()))
+			.to_srv_request();
+
+		assert_eq!(ClerkRequest::get_header(&request, "authorization"), Some("Bearer token".to_string()));
+		// values that aren't visible ascii are ignored rather than failing the request
+		assert_eq!(ClerkRequest::get_header(&request, "referer"), None);
+		assert_eq!(ClerkRequest::get_header(&request, "x-missing"), None);
+	}
+}
@@ -1,8 +1,29 @@
-use crate::{apis::jwks_api::JwksKey, validators::jwks::JwksProvider};
-use jsonwebtoken::{decode, decode_header, errors::Error as jwtError, Algorithm, DecodingKey, Header, Validation};
+use crate::{
+	apis::jwks_api::JwksKey,
+	validators::{
+		impersonation::{ImpersonationHook, ImpersonationPolicy},
+		jwks::JwksProvider,
+		request_state::{AuthReason, AuthenticateRequestOptions, RequestAuthenticator, RequestState},
+		reverification::{FactorVerificationAge, Reverification, ReverificationLevel},
+		session_status::SessionStatusVerifier,
+		token_extractor::{extract_token, ExtractedToken, TokenExtractor, TokenSource},
+	},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+	decode, decode_header,
+	errors::{Error as jwtError, ErrorKind},
+	Algorithm, DecodingKey, Header, Validation,
+};
+use serde::de::DeserializeOwned;
 use serde_json::{Map, Value};
-use std::{error::Error, fmt, sync::Arc};
+use std::{borrow::Cow, error::Error, fmt, marker::PhantomData, sync::Arc, time::Duration};
 
+/// The organization a session is active in.
+///
+/// Session tokens v1 carry it in the `org_*` claims, while v2 tokens carry it in the compact `o` claim, with the
+/// permissions encoded as a bitmap per organization feature. Both are decoded the same way, e.g. the role is always
+/// prefixed with `org:` and the permissions are always full keys like `org:invoices:read`.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ActiveOrganization {
 	#[serde(rename = "org_id")]
@@ -30,6 +51,102 @@ impl ActiveOrganization {
 	}
 }
 
+/// The compact `o` claim of session tokens v2.
+#[derive(Deserialize)]
+struct CompactOrganization {
+	id: String,
+	#[serde(rename = "slg")]
+	slug: Option<String>,
+	#[serde(rename = "rol")]
+	role: Option<String>,
+	/// The permission names, e.g. `read,manage`
+	#[serde(rename = "per")]
+	permissions: Option<String>,
+	/// The feature permission map, a bitmap of permission names per organization feature, e.g. `3,2`
+	#[serde(rename = "fpm")]
+	feature_permissions: Option<String>,
+}
+
+impl CompactOrganization {
+	fn into_active_organization(self, features: &[String]) -> ActiveOrganization {
+		let names: Vec<&str> = split_list(self.permissions.as_deref()).collect();
+		let bitmaps = split_list(self.feature_permissions.as_deref()).map(|bitmap| bitmap.parse::<u64>().unwrap_or(0));
+
+		// the n-th bitmap applies to the n-th organization feature, and its n-th bit to the n-th permission name
+		let mut permissions = Vec::new();
+		for (feature, bitmap) in features.iter().zip(bitmaps) {
+			for (index, name) in names.iter().enumerate().take(u64::BITS as usize) {
+				if bitmap & (1 << index) != 0 {
+					permissions.push(format!("org:{}:{}", feature, name));
+				}
+			}
+		}
+
+		ActiveOrganization {
+			id: self.id,
+			slug: self.slug.unwrap_or_default(),
+			role: self.role.map(|role| format!("org:{}", role)).unwrap_or_default(),
+			permissions,
+		}
+	}
+}
+
+/// Features (`fea`) or plans (`pla`) of a session token v2, split by the scope they apply to.
+///
+/// The claims list scoped names, e.g. `u:beta,o:dashboard`, where a name scoped with `ou` applies to both.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub struct Entitlements {
+	/// Names that apply to the user.
+	pub user: Vec<String>,
+	/// Names that apply to the active organization.
+	pub org: Vec<String>,
+}
+
+impl Entitlements {
+	pub fn is_empty(&self) -> bool {
+		self.user.is_empty() && self.org.is_empty()
+	}
+
+	/// Checks if the name applies to the user or the active organization.
+	pub fn contains(&self, name: &str) -> bool {
+		self.user.iter().chain(self.org.iter()).any(|val| val == name)
+	}
+}
+
+impl From<String> for Entitlements {
+	fn from(claim: String) -> Self {
+		let mut entitlements = Self::default();
+		for value in split_list(Some(&claim)) {
+			let Some((scope, name)) = value.split_once(':') else {
+				continue;
+			};
+
+			if scope.contains('u') {
+				entitlements.user.push(name.to_string());
+			}
+			if scope.contains('o') {
+				entitlements.org.push(name.to_string());
+			}
+		}
+
+		entitlements
+	}
+}
+
+impl From<Entitlements> for String {
+	fn from(entitlements: Entitlements) -> Self {
+		let user = entitlements.user.iter().map(|name| format!("u:{}", name));
+		let org = entitlements.org.iter().map(|name| format!("o:{}", name));
+		user.chain(org).collect::<Vec<_>>().join(",")
+	}
+}
+
+/// Splits a comma separated claim, e.g. `read,manage`.
+fn split_list(list: Option<&str>) -> impl Iterator<Item = &str> {
+	list.unwrap_or_default().split(',').map(str::trim).filter(|val| !val.is_empty())
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Actor {
 	pub iss: Option<String>,
@@ -37,8 +154,13 @@ pub struct Actor {
 	pub sub: String,
 }
 
+/// The claims of a verified Clerk session token.
+///
+/// Custom claims added with a JWT template can be deserialized into `C`, which is flattened into the token claims. A token
+/// missing any of the claims `C` requires fails validation.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-pub struct ClerkJwt {
+#[serde(from = "RawClerkJwt<C>")]
+pub struct ClerkJwt<C = ()> {
 	pub azp: Option<String>,
 	pub exp: i32,
 	pub iat: i32,
@@ -49,27 +171,271 @@ pub struct ClerkJwt {
 	pub act: Option<Actor>,
 	#[serde(flatten)]
 	pub org: Option<ActiveOrganization>,
+	/// The features of the user and the active organization (session token v2)
+	#[serde(rename = "fea", skip_serializing_if = "Entitlements::is_empty")]
+	pub features: Entitlements,
+	/// The plans of the user and the active organization (session token v2)
+	#[serde(rename = "pla", skip_serializing_if = "Entitlements::is_empty")]
+	pub plans: Entitlements,
+	/// How long ago the user verified their factors, see [`require_reverification`](Self::require_reverification)
+	#[serde(rename = "fva", skip_serializing_if = "Option::is_none")]
+	pub factor_verification_age: Option<FactorVerificationAge>,
+	/// The custom claims of the token, see [`ClerkAuthorizer`]
+	#[serde(flatten)]
+	pub claims: C,
 	/// Catch-all for any other attributes that may be present in the JWT. This
 	/// is useful for custom templates that may have additional fields
 	#[serde(flatten)]
 	pub other: Map<String, Value>,
+	/// Name of the Clerk instance whose key verified this token. This is only
+	/// set by providers that serve several instances, like [`CompositeJwksProvider`](crate::validators::jwks::CompositeJwksProvider)
+	#[serde(skip)]
+	pub instance: Option<String>,
+	/// Where the token was read from on the request. This is only set when
+	/// the token was extracted by a [`ClerkAuthorizer`]
+	#[serde(skip)]
+	pub token_source: Option<TokenSource>,
+}
+
+/// Who a feature or plan check applies to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AuthScope {
+	/// Either the user or the active organization.
+	#[default]
+	Any,
+	User,
+	Organization,
+}
+
+/// An authorization check on the claims of a session, see [`ClerkJwt::has`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthCheck {
+	/// The role in the active organization, e.g. `org:admin`.
+	Role(String),
+	/// A permission in the active organization, e.g. `org:invoices:read`.
+	Permission(String),
+	/// A billing feature, e.g. `export`.
+	Feature { name: String, scope: AuthScope },
+	/// A billing plan, e.g. `pro`.
+	Plan { name: String, scope: AuthScope },
+	/// The user verified their factors recently, see [`ClerkJwt::require_reverification`].
+	Reverification(Reverification),
+}
+
+impl AuthCheck {
+	pub fn role(role: &str) -> Self {
+		Self::Role(role.to_string())
+	}
+
+	pub fn permission(permission: &str) -> Self {
+		Self::Permission(permission.to_string())
+	}
+
+	/// A feature check, scoped with an `org:` or `user:` prefix like the other Clerk SDKs, e.g. `org:export`.
+	pub fn feature(feature: &str) -> Self {
+		let (name, scope) = parse_scope(feature);
+		Self::Feature { name, scope }
+	}
+
+	/// A plan check, scoped with an `org:` or `user:` prefix like the other Clerk SDKs, e.g. `user:pro`.
+	pub fn plan(plan: &str) -> Self {
+		let (name, scope) = parse_scope(plan);
+		Self::Plan { name, scope }
+	}
+
+	/// A step-up check, the user has to have verified the factors of the level within the max age.
+	pub fn reverification(level: ReverificationLevel, max_age: Duration) -> Self {
+		Self::Reverification(Reverification::new(level, max_age))
+	}
+}
+
+fn parse_scope(value: &str) -> (String, AuthScope) {
+	match value.split_once(':') {
+		Some(("org", name)) => (name.to_string(), AuthScope::Organization),
+		Some(("user", name)) => (name.to_string(), AuthScope::User),
+		_ => (value.to_string(), AuthScope::Any),
+	}
+}
+
+impl fmt::Display for AuthCheck {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let scoped = |kind: &str, name: &str, scope: &AuthScope| match scope {
+			AuthScope::Any => format!("{} {}", kind, name),
+			AuthScope::User => format!("{} user:{}", kind, name),
+			AuthScope::Organization => format!("{} org:{}", kind, name),
+		};
+
+		match self {
+			AuthCheck::Role(role) => write!(f, "role {}", role),
+			AuthCheck::Permission(permission) => write!(f, "permission {}", permission),
+			AuthCheck::Feature { name, scope } => write!(f, "{}", scoped("feature", name, scope)),
+			AuthCheck::Plan { name, scope } => write!(f, "{}", scoped("plan", name, scope)),
+			AuthCheck::Reverification(reverification) => write!(f, "reverification {:?}", reverification.level),
+		}
+	}
+}
+
+impl<C> ClerkJwt<C> {
+	/// Whether the session is impersonated, i.e. the token carries an `act` claim.
+	pub fn is_impersonated(&self) -> bool {
+		self.act.is_some()
+	}
+
+	/// The id of the user impersonating the subject, if any.
+	pub fn actor_id(&self) -> Option<&str> {
+		self.act.as_ref().map(|actor| actor.sub.as_str())
+	}
+
+	/// Checks the role, permissions, features or plans of the session.
+	///
+	/// Role and permission checks apply to the active organization, and fail when there is none.
+	pub fn has(&self, check: &AuthCheck) -> bool {
+		let has_scoped = |entitlements: &Entitlements, name: &str, scope: &AuthScope| match scope {
+			AuthScope::Any => entitlements.contains(name),
+			AuthScope::User => entitlements.user.iter().any(|val| val == name),
+			AuthScope::Organization => entitlements.org.iter().any(|val| val == name),
+		};
+
+		match check {
+			AuthCheck::Role(role) => self.org.as_ref().is_some_and(|org| org.has_role(role)),
+			AuthCheck::Permission(permission) => self.org.as_ref().is_some_and(|org| org.has_permission(permission)),
+			AuthCheck::Feature { name, scope } => has_scoped(&self.features, name, scope),
+			AuthCheck::Plan { name, scope } => has_scoped(&self.plans, name, scope),
+			AuthCheck::Reverification(reverification) => reverification.is_satisfied_by(self.factor_verification_age.as_ref()),
+		}
+	}
+
+	/// Returns a [`ClerkError::Forbidden`] error unless the session passes the check, see [`has`](Self::has).
+	///
+	/// Failed reverification checks return a [`ClerkError::ReverificationRequired`] error instead.
+	pub fn require(&self, check: &AuthCheck) -> Result<(), ClerkError> {
+		match (self.has(check), check) {
+			(true, _) => Ok(()),
+			(false, AuthCheck::Reverification(reverification)) => Err(ClerkError::ReverificationRequired(reverification.clone())),
+			(false, _) => Err(ClerkError::Forbidden(format!("Error: The session is missing the {}!", check))),
+		}
+	}
+
+	/// Requires the user to have verified the factors of the level within the max age, e.g. before changing payment
+	/// details. The error responds with the JSON the Clerk frontend SDKs answer with a reverification prompt.
+	pub fn require_reverification(&self, level: ReverificationLevel, max_age: Duration) -> Result<(), ClerkError> {
+		self.require(&AuthCheck::reverification(level, max_age))
+	}
+}
+
+/// The claims as they are found in session tokens of either version, before being unified into a [`ClerkJwt`].
+#[derive(Deserialize)]
+struct RawClerkJwt<C> {
+	azp: Option<String>,
+	exp: i32,
+	iat: i32,
+	iss: String,
+	nbf: i32,
+	sid: Option<String>,
+	sub: String,
+	act: Option<Actor>,
+	o: Option<CompactOrganization>,
+	#[serde(default)]
+	fea: Entitlements,
+	#[serde(default)]
+	pla: Entitlements,
+	fva: Option<FactorVerificationAge>,
+	#[serde(flatten)]
+	org: Option<ActiveOrganization>,
+	#[serde(flatten)]
+	claims: C,
+	#[serde(flatten)]
+	other: Map<String, Value>,
+}
+
+impl<C> From<RawClerkJwt<C>> for ClerkJwt<C> {
+	fn from(raw: RawClerkJwt<C>) -> Self {
+		let org = match raw.o {
+			Some(org) => Some(org.into_active_organization(&raw.fea.org)),
+			None => raw.org,
+		};
+
+		Self {
+			azp: raw.azp,
+			exp: raw.exp,
+			iat: raw.iat,
+			iss: raw.iss,
+			nbf: raw.nbf,
+			sid: raw.sid,
+			sub: raw.sub,
+			act: raw.act,
+			org,
+			features: raw.fea,
+			plans: raw.pla,
+			factor_verification_age: raw.fva,
+			claims: raw.claims,
+			other: raw.other,
+			instance: None,
+			token_source: None,
+		}
+	}
 }
 
 pub trait ClerkRequest {
 	fn get_header(&self, key: &str) -> Option<String>;
 	fn get_cookie(&self, key: &str) -> Option<String>;
+
+	/// Returns the path and query of the request, e.g. `/dashboard?tab=settings`.
+	///
+	/// This is used to send the browser back to the requested page after a handshake, which is skipped when it returns `None`.
+	fn get_uri(&self) -> Option<String> {
+		None
+	}
+
+	/// Returns the request method, e.g. `GET`. Only `GET` requests can go through a handshake, the method isn't checked when this returns `None`.
+	fn get_method(&self) -> Option<String> {
+		None
+	}
 }
 
 #[derive(Clone, Debug)]
 pub enum ClerkError {
 	Unauthorized(String),
+	/// The request is signed in, but doesn't pass an authorization check, see [`ClerkJwt::require`].
+	Forbidden(String),
+	/// The user has to verify their factors again, see [`ClerkJwt::require_reverification`].
+	ReverificationRequired(Reverification),
 	InternalServerError(String),
 }
 
+impl ClerkError {
+	/// The status code to respond with.
+	pub fn status_code(&self) -> u16 {
+		match self {
+			ClerkError::Unauthorized(_) => 401,
+			ClerkError::Forbidden(_) | ClerkError::ReverificationRequired(_) => 403,
+			ClerkError::InternalServerError(_) => 500,
+		}
+	}
+
+	/// The body to respond with, which is the JSON the Clerk frontend SDKs expect for reverifications.
+	pub fn message(&self) -> String {
+		match self {
+			ClerkError::Unauthorized(msg) | ClerkError::Forbidden(msg) | ClerkError::InternalServerError(msg) => msg.clone(),
+			ClerkError::ReverificationRequired(reverification) => reverification.error_body(),
+		}
+	}
+
+	/// The content type of the [`message`](Self::message), if it isn't plain text.
+	pub fn content_type(&self) -> Option<&'static str> {
+		match self {
+			ClerkError::ReverificationRequired(_) => Some("application/json"),
+			_ => None,
+		}
+	}
+}
+
 impl fmt::Display for ClerkError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
 			ClerkError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
+			ClerkError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
+			ClerkError::ReverificationRequired(reverification) => write!(f, "Forbidden: {}", reverification.error_body()),
 			ClerkError::InternalServerError(msg) => write!(f, "Internal Server Error: {}", msg),
 		}
 	}
@@ -77,9 +443,16 @@ impl fmt::Display for ClerkError {
 
 impl Error for ClerkError {}
 
-pub struct ClerkAuthorizer<J> {
+/// Authorizes requests, yielding a [`ClerkJwt`] with custom claims of type `C`.
+pub struct ClerkAuthorizer<J, C = ()> {
 	jwks_provider: Arc<J>,
 	validate_session_cookie: bool,
+	request_authenticator: Option<Arc<RequestAuthenticator>>,
+	session_status: Option<Arc<SessionStatusVerifier>>,
+	token_extractors: Option<Arc<[TokenExtractor]>>,
+	impersonation_policy: Arc<ImpersonationPolicy>,
+	impersonation_hook: Option<ImpersonationHook>,
+	_claims: PhantomData<fn() -> C>,
 }
 
 impl<J: JwksProvider> ClerkAuthorizer<J> {
@@ -88,8 +461,99 @@ impl<J: JwksProvider> ClerkAuthorizer<J> {
 		Self {
 			jwks_provider: Arc::new(jwks_provider),
 			validate_session_cookie,
+			request_authenticator: None,
+			session_status: None,
+			token_extractors: None,
+			impersonation_policy: Arc::new(ImpersonationPolicy::default()),
+			impersonation_hook: None,
+			_claims: PhantomData,
 		}
 	}
+}
+
+impl<J: JwksProvider, C: DeserializeOwned> ClerkAuthorizer<J, C> {
+	/// Deserializes the custom claims of the tokens into `C`, e.g. `authorizer.with_claims::<MyClaims>()`.
+	///
+	/// Tokens missing any of the claims required by `C` are rejected.
+	pub fn with_claims<T: DeserializeOwned>(self) -> ClerkAuthorizer<J, T> {
+		ClerkAuthorizer {
+			jwks_provider: self.jwks_provider,
+			validate_session_cookie: self.validate_session_cookie,
+			request_authenticator: self.request_authenticator,
+			session_status: self.session_status,
+			token_extractors: self.token_extractors,
+			impersonation_policy: self.impersonation_policy,
+			impersonation_hook: self.impersonation_hook,
+			_claims: PhantomData,
+		}
+	}
+
+	/// Enables the full request authentication flow of the Clerk SDKs for [`authenticate_request`](Self::authenticate_request).
+	///
+	/// Session cookies are compared with the `__client_uat` cookie, and browsers holding an expired or outdated session are
+	/// sent through a handshake to refresh it instead of being rejected. This implies validating the session cookie.
+	pub fn with_request_state(mut self, options: AuthenticateRequestOptions) -> Self {
+		self.validate_session_cookie = true;
+		self.request_authenticator = Some(Arc::new(RequestAuthenticator::new(options)));
+		self
+	}
+
+	/// Enables confirming that the session of a verified token is still active with the Backend API.
+	///
+	/// The check only runs on the routes the middlewares select with their session status scope, or when calling
+	/// [`verify_session_status`](Self::verify_session_status).
+	pub fn with_session_status(mut self, verifier: SessionStatusVerifier) -> Self {
+		self.session_status = Some(Arc::new(verifier));
+		self
+	}
+
+	/// Sets the ordered list of places the token is read from, the first token found is validated.
+	///
+	/// This replaces the default `Authorization: Bearer` header and `__session` cookie. With
+	/// [`with_request_state`](Self::with_request_state) the `__session` cookie is always checked against the
+	/// `__client_uat` cookie, after the other extractors found no token.
+	pub fn with_token_extractors(mut self, extractors: Vec<TokenExtractor>) -> Self {
+		self.token_extractors = Some(extractors.into());
+		self
+	}
+
+	/// Sets whether impersonated sessions are accepted, globally or per route. Rejected requests get a 403.
+	pub fn with_impersonation_policy(mut self, policy: ImpersonationPolicy) -> Self {
+		self.impersonation_policy = Arc::new(policy);
+		self
+	}
+
+	/// Calls the hook with the actor and the subject of every impersonated request the impersonation policy accepts.
+	///
+	/// # Example
+	/// ```
+	/// let authorizer = authorizer.on_impersonation(|actor, subject| {
+	///     log::info!("{} is acting as {}", actor.sub, subject);
+	/// });
+	/// ```
+	pub fn on_impersonation<F>(mut self, hook: F) -> Self
+	where
+		F: Fn(&Actor, &str) + Send + Sync + 'static,
+	{
+		self.impersonation_hook = Some(Arc::new(hook));
+		self
+	}
+
+	/// Returns the extractors tokens are read with, see [`with_token_extractors`](Self::with_token_extractors).
+	pub fn token_extractors(&self) -> Cow<'_, [TokenExtractor]> {
+		match &self.token_extractors {
+			Some(extractors) => Cow::Borrowed(extractors),
+			None => Cow::Owned(TokenExtractor::defaults(self.validate_session_cookie)),
+		}
+	}
+
+	/// Reads the token of a service request, along with where it came from.
+	pub fn extract_token<T>(&self, request: &T) -> Option<ExtractedToken>
+	where
+		T: ClerkRequest,
+	{
+		extract_token(&self.token_extractors(), request)
+	}
 
 	/// Returns a reference to the underlying [`JwksProvider`].
 	pub fn jwks_provider(&self) -> &Arc<J> {
@@ -97,39 +561,113 @@ impl<J: JwksProvider> ClerkAuthorizer<J> {
 	}
 
 	/// Authorizes a service request against the Clerk auth provider
-	pub async fn authorize<T>(&self, request: &T) -> Result<ClerkJwt, ClerkError>
+	pub async fn authorize<T>(&self, request: &T) -> Result<ClerkJwt<C>, ClerkError>
 	where
 		T: ClerkRequest,
 	{
-		// get the jwt from header or cookies
-		let access_token: String = match request.get_header("Authorization") {
-			Some(val) => val.to_string().replace("Bearer ", ""),
-			None => match self.validate_session_cookie {
-				true => match request.get_cookie("__session") {
-					Some(cookie) => cookie.to_string(),
-					None => {
-						return Err(ClerkError::Unauthorized(String::from(
-							"Error: No Authorization header or session cookie found on the request payload!",
-						)))
-					}
-				},
-				false => {
-					return Err(ClerkError::Unauthorized(String::from(
-						"Error: No Authorization header found on the request payload!",
-					)))
+		// get the jwt from the configured sources, by default the header or cookies
+		let Some(ExtractedToken { token, source }) = self.extract_token(request) else {
+			let message = match (&self.token_extractors, self.validate_session_cookie) {
+				(Some(_), _) => "Error: No token found on the request payload!",
+				(None, true) => "Error: No Authorization header or session cookie found on the request payload!",
+				(None, false) => "Error: No Authorization header found on the request payload!",
+			};
+			return Err(ClerkError::Unauthorized(String::from(message)));
+		};
+
+		let mut jwt = validate_jwt_with_claims(&token, self.jwks_provider.clone()).await?;
+		jwt.token_source = Some(source);
+		self.check_impersonation(&jwt, request)?;
+		Ok(jwt)
+	}
+
+	/// Applies the impersonation policy to an impersonated jwt, calling the hook when it is accepted.
+	fn check_impersonation<T>(&self, jwt: &ClerkJwt<C>, request: &T) -> Result<(), ClerkError>
+	where
+		T: ClerkRequest,
+	{
+		let Some(actor) = &jwt.act else {
+			return Ok(());
+		};
+
+		let uri = request.get_uri();
+		let path = uri.as_deref().map(|uri| uri.split_once('?').map_or(uri, |(path, _)| path));
+		if !self.impersonation_policy.allows(path) {
+			return Err(ClerkError::Forbidden(String::from(
+				"Error: Impersonated sessions are not allowed on this route!",
+			)));
+		}
+
+		if let Some(hook) = &self.impersonation_hook {
+			hook(actor, &jwt.sub);
+		}
+		Ok(())
+	}
+
+	/// Authenticates a service request, resolving whether it is signed in, signed out or needs a handshake.
+	///
+	/// Without [`with_request_state`](Self::with_request_state) this only reports the result of [`authorize`](Self::authorize).
+	pub async fn authenticate_request<T>(&self, request: &T) -> RequestState<C>
+	where
+		T: ClerkRequest,
+	{
+		match &self.request_authenticator {
+			Some(authenticator) => {
+				let state = authenticator.authenticate(&self.jwks_provider, &self.token_extractors(), request).await;
+				match state.jwt.as_ref().map(|jwt| self.check_impersonation(jwt, request)) {
+					Some(Err(error)) => RequestState::signed_out(Some(AuthReason::ImpersonationNotAllowed), error).with_headers(state.headers),
+					_ => state,
 				}
+			}
+			None => match self.authorize(request).await {
+				Ok(jwt) => RequestState::signed_in(jwt),
+				Err(error) => RequestState::signed_out(None, error),
 			},
-		};
+		}
+	}
+
+	/// Confirms that the session the jwt was issued for is still active, see [`with_session_status`](Self::with_session_status).
+	pub async fn verify_session_status(&self, jwt: &ClerkJwt<C>) -> Result<(), ClerkError> {
+		match &self.session_status {
+			Some(verifier) => verifier.verify(jwt).await,
+			None => Err(ClerkError::InternalServerError(String::from(
+				"Error: Session status verification is not configured!",
+			))),
+		}
+	}
+
+	/// Authenticates a service request, optionally checking the session status of signed in requests.
+	#[cfg(any(feature = "actix", feature = "tower", feature = "rocket", feature = "poem"))]
+	pub(crate) async fn authenticate_request_with_status<T>(&self, request: &T, verify_session_status: bool) -> RequestState<C>
+	where
+		T: ClerkRequest,
+	{
+		let state = self.authenticate_request(request).await;
+		if !verify_session_status {
+			return state;
+		}
 
-		validate_jwt(&access_token, self.jwks_provider.clone()).await
+		match &state.jwt {
+			Some(jwt) => match self.verify_session_status(jwt).await {
+				Ok(()) => state,
+				Err(error) => RequestState::signed_out(Some(AuthReason::SessionNotActive), error).with_headers(state.headers),
+			},
+			None => state,
+		}
 	}
 }
 
-impl<J> Clone for ClerkAuthorizer<J> {
+impl<J, C> Clone for ClerkAuthorizer<J, C> {
 	fn clone(&self) -> Self {
 		Self {
 			jwks_provider: self.jwks_provider.clone(),
 			validate_session_cookie: self.validate_session_cookie,
+			request_authenticator: self.request_authenticator.clone(),
+			session_status: self.session_status.clone(),
+			token_extractors: self.token_extractors.clone(),
+			impersonation_policy: self.impersonation_policy.clone(),
+			impersonation_hook: self.impersonation_hook.clone(),
+			_claims: PhantomData,
 		}
 	}
 }
@@ -138,6 +676,20 @@ impl<J> Clone for ClerkAuthorizer<J> {
 ///
 /// The jwt is required to have a `kid` which is used to request the matching key from the provider.
 pub async fn validate_jwt<J: JwksProvider>(token: &str, jwks: Arc<J>) -> Result<ClerkJwt, ClerkError> {
+	validate_jwt_with_claims(token, jwks).await
+}
+
+/// Validates a jwt using the given [`JwksProvider`], deserializing its custom claims into `C`.
+pub async fn validate_jwt_with_claims<J: JwksProvider, C: DeserializeOwned>(token: &str, jwks: Arc<J>) -> Result<ClerkJwt<C>, ClerkError> {
+	let (key, instance) = get_token_key(token, jwks.as_ref()).await?;
+
+	let mut jwt = validate_jwt_with_key_and_claims(token, &key)?;
+	jwt.instance = instance;
+	Ok(jwt)
+}
+
+/// Requests the key a jwt was signed with from the given [`JwksProvider`], along with the name of the instance it belongs to.
+pub(crate) async fn get_token_key<J: JwksProvider>(token: &str, jwks: &J) -> Result<(JwksKey, Option<String>), ClerkError> {
 	// parse the header to get the kid
 	let kid = match get_token_header(token).map(|h| h.kid) {
 		Ok(Some(kid)) => kid,
@@ -147,31 +699,46 @@ pub async fn validate_jwt<J: JwksProvider>(token: &str, jwks: Arc<J>) -> Result<
 		}
 	};
 
-	// get the key from the provider
-	let Ok(key) = jwks.get_key(&kid).await else {
+	// get the key from the provider, the issuer lets providers serving several instances pick the right one
+	let issuer = get_unverified_claims(token).and_then(|claims| claims.iss);
+	let Ok((key, instance)) = jwks.get_instance_key(&kid, issuer.as_deref()).await else {
 		// In the event that a matching jwk was not found we want to output an error
 		return Err(ClerkError::Unauthorized(String::from("Error: Invalid JWT!")));
 	};
 
-	validate_jwt_with_key(token, &key)
+	Ok((key, instance))
 }
 
 /// Validates a jwt using the given jwk.
 ///
 /// This function does not check that the token's kid matches the key's.
 pub fn validate_jwt_with_key(token: &str, key: &JwksKey) -> Result<ClerkJwt, ClerkError> {
+	validate_jwt_with_key_and_claims(token, key)
+}
+
+/// Validates a jwt using the given jwk, deserializing its custom claims into `C`.
+pub fn validate_jwt_with_key_and_claims<C: DeserializeOwned>(token: &str, key: &JwksKey) -> Result<ClerkJwt<C>, ClerkError> {
+	let mut validation = Validation::new(Algorithm::RS256);
+	validation.validate_exp = true;
+	validation.validate_nbf = true;
+
+	decode_with_key(token, key, &validation)
+}
+
+/// Decodes the claims of a jwt using the given jwk and validation rules.
+pub(crate) fn decode_with_key<C: DeserializeOwned>(token: &str, key: &JwksKey, validation: &Validation) -> Result<C, ClerkError> {
 	match key.alg.as_str() {
 		// Currently, clerk only supports Rs256 by default
 		"RS256" => {
 			let decoding_key = DecodingKey::from_rsa_components(&key.n, &key.e)
 				.map_err(|_| ClerkError::InternalServerError(String::from("Error: Invalid decoding key")))?;
 
-			let mut validation = Validation::new(Algorithm::RS256);
-			validation.validate_exp = true;
-			validation.validate_nbf = true;
-
-			match decode::<ClerkJwt>(token, &decoding_key, &validation) {
+			match decode::<C>(token, &decoding_key, validation) {
 				Ok(token) => Ok(token.claims),
+				// e.g. the token is missing custom claims the caller requires
+				Err(err) if matches!(err.kind(), ErrorKind::Json(_)) => {
+					Err(ClerkError::Unauthorized(format!("Error: Invalid JWT claims! cause: {}", err)))
+				}
 				Err(err) => Err(ClerkError::Unauthorized(format!("Error: Invalid JWT! cause: {}", err))),
 			}
 		}
@@ -185,21 +752,51 @@ fn get_token_header(token: &str) -> Result<Header, jwtError> {
 	header
 }
 
+/// Claims that are read before a jwt is verified
+#[derive(Deserialize)]
+pub(crate) struct UnverifiedClaims {
+	pub iss: Option<String>,
+	pub exp: Option<i64>,
+}
+
+/// Extract the claims from a jwt token without verifying it
+pub(crate) fn get_unverified_claims(token: &str) -> Option<UnverifiedClaims> {
+	let payload = token.split('.').nth(1)?;
+	let payload = URL_SAFE_NO_PAD.decode(payload).ok()?;
+	serde_json::from_slice::<UnverifiedClaims>(&payload).ok()
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 	use super::*;
-	use crate::{apis::jwks_api::JwksKey, validators::jwks::tests::StaticJwksProvider};
-	use base64::engine::general_purpose::URL_SAFE_NO_PAD;
-	use base64::prelude::*;
+	use crate::{
+		apis::jwks_api::JwksKey,
+		validators::{
+			jwks::{tests::StaticJwksProvider, CompositeJwksProvider, CompositeStrategy, JwksInstance},
+			request_state::tests::MockRequest,
+		},
+	};
 	use jsonwebtoken::{encode, errors::ErrorKind, Algorithm, EncodingKey, Header};
 	use rsa::{pkcs1::EncodeRsaPrivateKey, traits::PublicKeyParts, RsaPrivateKey};
 	use std::time::{SystemTime, UNIX_EPOCH};
 
-	#[derive(Debug, serde::Serialize)]
+	#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 	struct CustomFields {
 		custom_attribute: String,
 	}
 
+	#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+	struct TemplateClaims {
+		custom_key: String,
+		custom_map: CustomFields,
+	}
+
+	#[derive(Debug, serde::Deserialize)]
+	struct RequiredClaims {
+		#[allow(dead_code)]
+		tenant_id: String,
+	}
+
 	#[derive(Debug, serde::Serialize)]
 	struct Claims {
 		sub: String,
@@ -218,7 +815,7 @@ mod tests {
 		custom_map: CustomFields,
 	}
 
-	struct Helper {
+	pub(crate) struct Helper {
 		private_key: RsaPrivateKey,
 	}
 
@@ -232,9 +829,6 @@ mod tests {
 		}
 
 		pub fn generate_jwt_token(&self, kid: Option<&str>, current_time: Option<usize>, expired: bool) -> String {
-			let pem = self.private_key.to_pkcs1_pem(rsa::pkcs8::LineEnding::LF).unwrap();
-			let encoding_key = EncodingKey::from_rsa_pem(pem.as_bytes()).expect("Failed to load encoding key");
-
 			let mut current_time = current_time.unwrap_or(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as usize);
 
 			if expired {
@@ -268,12 +862,19 @@ mod tests {
 				},
 			};
 
+			self.sign(kid, &claims)
+		}
+
+		pub fn sign<C: serde::Serialize>(&self, kid: Option<&str>, claims: &C) -> String {
+			let pem = self.private_key.to_pkcs1_pem(rsa::pkcs8::LineEnding::LF).unwrap();
+			let encoding_key = EncodingKey::from_rsa_pem(pem.as_bytes()).expect("Failed to load encoding key");
+
 			let mut header = Header::new(Algorithm::RS256);
 			if let Some(kid_value) = kid {
 				header.kid = Some(kid_value.to_string());
 			}
 
-			let token = encode(&header, &claims, &encoding_key).expect("Failed to create jwt token");
+			let token = encode(&header, claims, &encoding_key).expect("Failed to create jwt token");
 
 			token
 		}
@@ -283,6 +884,19 @@ mod tests {
 			let encoded_exponent = URL_SAFE_NO_PAD.encode(self.private_key.e().to_bytes_be().as_slice());
 			(encoded_modulus, encoded_exponent)
 		}
+
+		pub fn jwks_key(&self, kid: &str) -> JwksKey {
+			let (modulus, exponent) = self.get_modulus_and_public_exponent();
+
+			JwksKey {
+				use_key: String::new(),
+				kty: String::new(),
+				kid: kid.to_string(),
+				alg: String::from("RS256"),
+				n: modulus,
+				e: exponent,
+			}
+		}
 	}
 
 	#[test]
@@ -324,6 +938,10 @@ mod tests {
 				role: "org_role".to_string(),
 				permissions: vec!["org_permission".to_string()],
 			}),
+			features: Entitlements::default(),
+			plans: Entitlements::default(),
+			factor_verification_age: None,
+			claims: (),
 			other: {
 				let mut map = Map::new();
 				map.insert("custom_key".to_string(), Value::String("custom_value".to_string()));
@@ -337,6 +955,8 @@ mod tests {
 				);
 				map
 			},
+			instance: None,
+			token_source: None,
 		};
 
 		assert_eq!(validate_jwt_with_key(token.as_str(), &jwks_key).expect("should be valid"), expected);
@@ -477,6 +1097,10 @@ mod tests {
 				role: "org_role".to_string(),
 				permissions: vec!["org_permission".to_string()],
 			}),
+			features: Entitlements::default(),
+			plans: Entitlements::default(),
+			factor_verification_age: None,
+			claims: (),
 			other: {
 				let mut map = Map::new();
 				map.insert("custom_key".to_string(), Value::String("custom_value".to_string()));
@@ -490,11 +1114,41 @@ mod tests {
 				);
 				map
 			},
+			instance: None,
+			token_source: None,
 		};
 
 		assert_eq!(validate_jwt(token.as_str(), jwks).await.expect("should be valid"), expected);
 	}
 
+	#[tokio::test]
+	async fn test_validate_jwt_tags_instance() {
+		let helper = Helper::new();
+
+		let kid = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+
+		let (modulus, exponent) = helper.get_modulus_and_public_exponent();
+
+		let jwks_key = JwksKey {
+			use_key: String::new(),
+			kty: String::new(),
+			kid: kid.to_string(),
+			alg: String::from("RS256"),
+			n: modulus,
+			e: exponent,
+		};
+		// the helper issues tokens with "issuer" as their iss claim
+		let jwks = Arc::new(
+			CompositeJwksProvider::new(CompositeStrategy::ByIssuer)
+				.with_instance(JwksInstance::new("production", StaticJwksProvider::from_key(jwks_key)).with_issuer("issuer")),
+		);
+
+		let token = helper.generate_jwt_token(Some(kid), None, false);
+
+		let jwt = validate_jwt(&token, jwks).await.expect("should be valid");
+		assert_eq!(jwt.instance.as_deref(), Some("production"));
+	}
+
 	#[tokio::test]
 	async fn test_validate_jwt_invalid_token() {
 		let helper = Helper::new();
@@ -560,6 +1214,232 @@ mod tests {
 		assert!(matches!(validate_jwt(&token, jwks).await, Err(ClerkError::Unauthorized(_))))
 	}
 
+	#[tokio::test]
+	async fn test_validate_jwt_with_claims() {
+		let helper = Helper::new();
+
+		let kid = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+		let jwks = Arc::new(StaticJwksProvider::from_key(helper.jwks_key(kid)));
+
+		let token = helper.generate_jwt_token(Some(kid), None, false);
+
+		let jwt: ClerkJwt<TemplateClaims> = validate_jwt_with_claims(&token, jwks).await.expect("should be valid");
+		assert_eq!(
+			jwt.claims,
+			TemplateClaims {
+				custom_key: "custom_value".to_string(),
+				custom_map: CustomFields {
+					custom_attribute: "custom_attribute".to_string(),
+				},
+			}
+		);
+		assert_eq!(jwt.sub, "user");
+	}
+
+	#[tokio::test]
+	async fn test_validate_jwt_session_token_v2() {
+		let helper = Helper::new();
+
+		let kid = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+		let jwks = Arc::new(StaticJwksProvider::from_key(helper.jwks_key(kid)));
+
+		let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+		let token = helper.sign(
+			Some(kid),
+			&serde_json::json!({
+				"v": 2,
+				"sub": "user",
+				"iss": "issuer",
+				"iat": current_time,
+				"nbf": current_time,
+				"exp": current_time + 1000,
+				"sid": "session_id",
+				"fea": "o:dashboard,o:impersonation,u:beta",
+				"pla": "u:free,o:pro",
+				"o": {
+					"id": "org_id",
+					"slg": "org_slug",
+					"rol": "admin",
+					"per": "read,manage",
+					"fpm": "3,2"
+				}
+			}),
+		);
+
+		let jwt = validate_jwt(&token, jwks).await.expect("should be valid");
+		let org = jwt.org.expect("should have an active organization");
+
+		assert_eq!(org.id, "org_id");
+		assert_eq!(org.slug, "org_slug");
+		assert!(org.has_role("org:admin"));
+		assert_eq!(
+			org.permissions,
+			vec!["org:dashboard:read", "org:dashboard:manage", "org:impersonation:manage"]
+		);
+		assert!(org.has_permission("org:impersonation:manage"));
+		assert!(!org.has_permission("org:impersonation:read"));
+
+		assert_eq!(jwt.features.user, vec!["beta"]);
+		assert_eq!(jwt.features.org, vec!["dashboard", "impersonation"]);
+		assert!(jwt.plans.contains("pro"));
+		// the v2 claims are not left in the catch-all
+		assert!(!jwt.other.contains_key("o") && !jwt.other.contains_key("fea"));
+	}
+
+	#[test]
+	fn test_has_checks() {
+		let jwt: ClerkJwt = serde_json::from_value(serde_json::json!({
+			"sub": "user",
+			"iss": "issuer",
+			"iat": 0,
+			"nbf": 0,
+			"exp": 0,
+			"org_id": "org_id",
+			"org_slug": "org_slug",
+			"org_role": "org:admin",
+			"org_permissions": ["org:invoices:read"],
+			"fea": "u:beta,o:export",
+			"pla": "o:pro"
+		}))
+		.unwrap();
+
+		assert!(jwt.has(&AuthCheck::role("org:admin")));
+		assert!(!jwt.has(&AuthCheck::role("org:member")));
+		assert!(jwt.has(&AuthCheck::permission("org:invoices:read")));
+		assert!(!jwt.has(&AuthCheck::permission("org:invoices:manage")));
+
+		assert!(jwt.has(&AuthCheck::feature("beta")));
+		assert!(jwt.has(&AuthCheck::feature("user:beta")));
+		assert!(!jwt.has(&AuthCheck::feature("org:beta")));
+		assert!(jwt.has(&AuthCheck::feature("org:export")));
+		assert!(jwt.has(&AuthCheck::plan("pro")));
+		assert!(!jwt.has(&AuthCheck::plan("user:pro")));
+
+		match jwt.require(&AuthCheck::plan("user:pro")) {
+			Err(error @ ClerkError::Forbidden(_)) => assert_eq!(error.status_code(), 403),
+			res => panic!("expected the check to fail, got {res:?}"),
+		}
+	}
+
+	#[test]
+	fn test_require_reverification() {
+		let jwt: ClerkJwt = serde_json::from_value(serde_json::json!({
+			"sub": "user",
+			"iss": "issuer",
+			"iat": 0,
+			"nbf": 0,
+			"exp": 0,
+			"fva": [30, -1]
+		}))
+		.unwrap();
+
+		assert_eq!(
+			jwt.factor_verification_age,
+			Some(FactorVerificationAge {
+				first_factor: Some(30),
+				second_factor: None,
+			})
+		);
+		assert!(jwt
+			.require_reverification(ReverificationLevel::FirstFactor, Duration::from_secs(60 * 60))
+			.is_ok());
+
+		match jwt.require_reverification(ReverificationLevel::SecondFactor, Duration::from_secs(10 * 60)) {
+			Err(error @ ClerkError::ReverificationRequired(_)) => {
+				assert_eq!(error.status_code(), 403);
+				assert_eq!(error.content_type(), Some("application/json"));
+				assert_eq!(error.message(), Reverification::strict().error_body());
+			}
+			res => panic!("expected the reverification to be required, got {res:?}"),
+		}
+	}
+
+	#[test]
+	fn test_entitlements_scopes() {
+		let entitlements = Entitlements::from(String::from("u:beta, ou:export,o:dashboard,invalid"));
+
+		assert_eq!(entitlements.user, vec!["beta", "export"]);
+		assert_eq!(entitlements.org, vec!["export", "dashboard"]);
+		assert_eq!(String::from(entitlements), "u:beta,u:export,o:export,o:dashboard");
+	}
+
+	#[tokio::test]
+	async fn test_authorize_missing_custom_claims() {
+		let helper = Helper::new();
+
+		let kid = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+		let authorizer = ClerkAuthorizer::new(StaticJwksProvider::from_key(helper.jwks_key(kid)), false).with_claims::<RequiredClaims>();
+
+		let token = helper.generate_jwt_token(Some(kid), None, false);
+		let request = MockRequest::default().header("Authorization", &format!("Bearer {token}"));
+
+		match authorizer.authorize(&request).await {
+			Err(ClerkError::Unauthorized(msg)) => assert!(msg.starts_with("Error: Invalid JWT claims!"), "{msg}"),
+			res => panic!("expected the token to be rejected, got {res:?}"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_authorize_with_token_extractors() {
+		let helper = Helper::new();
+
+		let kid = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+		let authorizer = ClerkAuthorizer::new(StaticJwksProvider::from_key(helper.jwks_key(kid)), false)
+			.with_token_extractors(vec![TokenExtractor::bearer(), TokenExtractor::query_param("token")]);
+
+		let token = helper.generate_jwt_token(Some(kid), None, false);
+		let request = MockRequest::default().uri(&format!("/events?token={token}"));
+
+		let jwt = authorizer.authorize(&request).await.expect("should be valid");
+		assert_eq!(jwt.token_source, Some(TokenSource::QueryParam(String::from("token"))));
+
+		// the session cookie is no longer read
+		let request = MockRequest::default().uri("/events").cookie("__session", &token);
+		assert!(matches!(authorizer.authorize(&request).await, Err(ClerkError::Unauthorized(_))));
+	}
+
+	#[tokio::test]
+	async fn test_authorize_impersonation_policy() {
+		let helper = Helper::new();
+
+		let kid = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+		let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+		let token = helper.sign(
+			Some(kid),
+			&serde_json::json!({
+				"sub": "user",
+				"iss": "issuer",
+				"iat": current_time,
+				"nbf": current_time,
+				"exp": current_time + 1000,
+				"act": { "sub": "support_agent" }
+			}),
+		);
+
+		let audit_log = Arc::new(std::sync::Mutex::new(Vec::new()));
+		let hook_log = audit_log.clone();
+		let authorizer = ClerkAuthorizer::new(StaticJwksProvider::from_key(helper.jwks_key(kid)), false)
+			.with_impersonation_policy(ImpersonationPolicy::RejectRoutes(vec![String::from("/account/delete")]))
+			.on_impersonation(move |actor, subject| hook_log.lock().unwrap().push(format!("{} as {}", actor.sub, subject)));
+
+		let request = |uri: &str| MockRequest::default().header("Authorization", &format!("Bearer {token}")).uri(uri);
+		let jwt = authorizer.authorize(&request("/account?tab=profile")).await.expect("should be valid");
+		assert!(jwt.is_impersonated());
+		assert_eq!(jwt.actor_id(), Some("support_agent"));
+
+		match authorizer.authorize(&request("/account/delete?confirm=true")).await {
+			Err(error @ ClerkError::Forbidden(_)) => assert_eq!(error.status_code(), 403),
+			res => panic!("expected the impersonated session to be rejected, got {res:?}"),
+		}
+		for uri in ["/account/%64elete", "/account/delete/", "//account/delete"] {
+			assert!(
+				matches!(authorizer.authorize(&request(uri)).await, Err(ClerkError::Forbidden(_))),
+				"{uri}"
+			);
+		}
+		assert_eq!(*audit_log.lock().unwrap(), vec![String::from("support_agent as user")]);
+	}
+
 	#[test]
 	fn test_helper_generate_token_header() {
 		let helper = Helper::new();
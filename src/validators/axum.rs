@@ -1,17 +1,9 @@
-use crate::validators::{
-	authorizer::{ClerkAuthorizer, ClerkError, ClerkRequest},
-	jwks::JwksProvider,
-};
+use crate::validators::{authorizer::ClerkRequest, tower};
 use axum::{
-	body::Body,
 	extract::Request,
-	http::{HeaderMap, HeaderValue, StatusCode},
-	response::Response,
+	http::{HeaderMap, HeaderValue},
 };
 use axum_extra::extract::cookie::CookieJar;
-use futures_util::future::BoxFuture;
-use std::task::{Context, Poll};
-use tower::{Layer, Service};
 
 pub struct AxumClerkRequest {
 	pub headers: HeaderMap<HeaderValue>,
@@ -65,119 +57,22 @@ impl ClerkRequest for AxumClerkRequest {
 ///     axum::serve(listener, app).await
 /// }
 /// ```
-pub struct ClerkLayer<J> {
-	authorizer: ClerkAuthorizer<J>,
-	routes: Option<Vec<String>>,
-}
-
-impl<J: JwksProvider> ClerkLayer<J> {
-	pub fn new(jwks_provider: J, routes: Option<Vec<String>>, validate_session_cookie: bool) -> Self {
-		let authorizer = ClerkAuthorizer::new(jwks_provider, validate_session_cookie);
-		Self { authorizer, routes }
-	}
-}
-
-impl<S, J> Layer<S> for ClerkLayer<J> {
-	type Service = ClerkMiddleware<S, J>;
-
-	fn layer(&self, service: S) -> Self::Service {
-		ClerkMiddleware {
-			service,
-			authorizer: self.authorizer.clone(),
-			routes: self.routes.clone(),
-		}
-	}
-}
-
-impl<J> Clone for ClerkLayer<J> {
-	fn clone(&self) -> Self {
-		Self {
-			authorizer: self.authorizer.clone(),
-			routes: self.routes.clone(),
-		}
-	}
-}
-
-pub struct ClerkMiddleware<S, J> {
-	service: S,
-	authorizer: ClerkAuthorizer<J>,
-	routes: Option<Vec<String>>,
-}
-
-impl<S, J> Service<Request> for ClerkMiddleware<S, J>
-where
-	S: Service<Request, Response = Response> + Send + 'static + Clone,
-	S::Future: Send + 'static,
-	J: JwksProvider + Send + Sync + 'static,
-{
-	type Response = S::Response;
-	type Error = S::Error;
-	type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
-
-	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-		self.service.poll_ready(cx)
-	}
-
-	fn call(&mut self, mut request: Request) -> Self::Future {
-		let mut svc = self.service.clone();
-
-		// We want to skip running the validator if we are not able to find a matching path from the listed valid paths provided by the user
-		match &self.routes {
-			Some(route_matches) => {
-				// If the user only wants to apply authentication to a select amount of routes, we handle that logic here
-				let path = request.uri().path();
-				// Check if the path was NOT contained inside of the routes specified by the user...
-				let path_not_in_specified_routes = route_matches.iter().find(|&route| route == path).is_none();
-
-				if path_not_in_specified_routes {
-					// Since the path was not inside of the listed routes we want to trigger an early exit
-					return Box::pin(async move {
-						let res = svc.call(request).await?;
-						return Ok(res);
-					});
-				}
-			}
-			// Since we did find a matching route we can simply do nothing here and start the actual auth logic...
-			None => {}
-		}
+///
+/// This is the framework-agnostic [`tower::ClerkLayer`] applied to axum's request and body types, so requests are
+/// authorized exactly the same way as with any other `http` based stack.
+pub type ClerkLayer<J, C = ()> = tower::ClerkLayer<J, C>;
 
-		let authorizer = self.authorizer.clone();
-		let req = AxumClerkRequest::from_axum_request(&request);
+pub type ClerkMiddleware<S, J, C = ()> = tower::ClerkMiddleware<S, J, C>;
 
-		Box::pin(async move {
-			// Check if the request is authenticated
-			match authorizer.authorize(&req).await {
-				// We have authed request and can pass the user onto the next body
-				Ok(jwt) => {
-					request.extensions_mut().insert(jwt);
-					let res = svc.call(request).await?;
-					return Ok(res);
-				}
-				// Output any other errors thrown from the Clerk authorizer
-				Err(error) => {
-					match error {
-						ClerkError::Unauthorized(msg) => {
-							return Ok(Response::builder().status(StatusCode::UNAUTHORIZED).body(Body::from(msg)).unwrap())
-						}
-						ClerkError::InternalServerError(msg) => {
-							return Ok(Response::builder()
-								.status(StatusCode::INTERNAL_SERVER_ERROR)
-								.body(Body::from(msg))
-								.unwrap())
-						}
-					};
-				}
-			}
-		})
-	}
-}
+/// Axum layer that only lets requests through whose session passes an
+/// [`AuthCheck`](crate::validators::authorizer::AuthCheck), responding with a 403 otherwise.
+///
+/// # Example
+/// ```
+/// let app = Router::new()
+///     .route("/billing", get(billing).layer(AuthCheckLayer::new(AuthCheck::permission("org:billing:manage"))))
+///     .layer(ClerkLayer::new(MemoryCacheJwksProvider::new(clerk), None, true));
+/// ```
+pub type AuthCheckLayer<C = ()> = tower::AuthCheckLayer<C>;
 
-impl<S: Clone, J> Clone for ClerkMiddleware<S, J> {
-	fn clone(&self) -> Self {
-		Self {
-			service: self.service.clone(),
-			authorizer: self.authorizer.clone(),
-			routes: self.routes.clone(),
-		}
-	}
-}
+pub type AuthCheckMiddleware<S, C = ()> = tower::AuthCheckMiddleware<S, C>;
@@ -0,0 +1,157 @@
+use crate::validators::{
+	authorizer::{decode_with_key, get_token_key, ClerkError, ClerkRequest},
+	jwks::JwksProvider,
+	request_state::get_query_param,
+};
+use jsonwebtoken::{Algorithm, Validation};
+
+/// Name of the query param and cookie the Frontend API passes the handshake payload back with.
+pub const HANDSHAKE_PARAM: &str = "__clerk_handshake";
+
+/// A `Set-Cookie` directive carried by a handshake payload.
+///
+/// The directives are issued by the Frontend API for the app's domain, so they can be sent back to the browser as is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CookieDirective {
+	pub name: String,
+	pub value: String,
+	/// The full directive, to be used as the value of a `Set-Cookie` header.
+	pub header: String,
+}
+
+impl CookieDirective {
+	/// Parses a `Set-Cookie` directive, e.g. `__session=ey...; Path=/; SameSite=Lax`.
+	pub fn parse(header: &str) -> Option<Self> {
+		let pair = header.split(';').next()?;
+		let (name, value) = pair.split_once('=')?;
+
+		Some(Self {
+			name: name.trim().to_string(),
+			value: value.trim().to_string(),
+			header: header.to_string(),
+		})
+	}
+}
+
+/// The verified payload of a handshake token.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HandshakePayload {
+	pub directives: Vec<CookieDirective>,
+}
+
+impl HandshakePayload {
+	/// The refreshed session token, if the handshake didn't sign the browser out.
+	pub fn session_token(&self) -> Option<&str> {
+		self.directives
+			.iter()
+			.rev()
+			.find(|directive| directive.name == "__session")
+			.map(|directive| directive.value.as_str())
+			.filter(|value| !value.is_empty())
+	}
+}
+
+#[derive(Deserialize)]
+struct HandshakeClaims {
+	handshake: Vec<String>,
+}
+
+/// Verifies a handshake token with the given [`JwksProvider`] and decodes the cookie directives it carries.
+pub async fn verify_handshake_token<J: JwksProvider>(token: &str, jwks: &J) -> Result<HandshakePayload, ClerkError> {
+	let (key, _) = get_token_key(token, jwks).await?;
+
+	// handshake tokens travel in urls, where they can be logged, so they have to expire
+	let mut validation = Validation::new(Algorithm::RS256);
+	validation.set_required_spec_claims(&["exp"]);
+	validation.validate_nbf = true;
+
+	let claims: HandshakeClaims = decode_with_key(token, &key, &validation)?;
+	let directives = claims.handshake.iter().filter_map(|header| CookieDirective::parse(header)).collect();
+
+	Ok(HandshakePayload { directives })
+}
+
+/// Where a handshake token was found on the request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakeTokenSource {
+	Cookie,
+	/// Development instances can't set cookies on the app's domain, so they pass the token in the url.
+	QueryParam,
+}
+
+/// Returns the handshake token of the request, if it is coming back from a handshake.
+pub fn get_handshake_token<T: ClerkRequest>(request: &T) -> Option<(String, HandshakeTokenSource)> {
+	if let Some(token) = request.get_cookie(HANDSHAKE_PARAM) {
+		return Some((token, HandshakeTokenSource::Cookie));
+	}
+
+	let uri = request.get_uri()?;
+	get_query_param(&uri, HANDSHAKE_PARAM).map(|token| (token, HandshakeTokenSource::QueryParam))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::validators::{authorizer::tests::Helper, jwks::tests::StaticJwksProvider};
+	use serde_json::Value;
+	use std::time::{SystemTime, UNIX_EPOCH};
+
+	const KID: &str = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+
+	/// Signs a handshake token carrying the given cookie directives, expiring in a minute.
+	pub(crate) fn sign_handshake(helper: &Helper, directives: Value) -> String {
+		let exp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 60;
+		helper.sign(Some(KID), &serde_json::json!({ "handshake": directives, "exp": exp }))
+	}
+
+	#[test]
+	fn test_parse_cookie_directive() {
+		let directive = CookieDirective::parse("__session=abc.def; Path=/; SameSite=Lax; Secure").expect("should be valid");
+
+		assert_eq!(directive.name, "__session");
+		assert_eq!(directive.value, "abc.def");
+		assert_eq!(directive.header, "__session=abc.def; Path=/; SameSite=Lax; Secure");
+		assert_eq!(CookieDirective::parse("invalid"), None);
+	}
+
+	#[tokio::test]
+	async fn test_verify_handshake_token() {
+		let helper = Helper::new();
+		let jwks = StaticJwksProvider::from_key(helper.jwks_key(KID));
+		let token = sign_handshake(
+			&helper,
+			serde_json::json!(["__session=new_token; Path=/", "__client_uat=1700000000; Path=/"]),
+		);
+
+		let payload = verify_handshake_token(&token, &jwks).await.expect("should be valid");
+		assert_eq!(payload.directives.len(), 2);
+		assert_eq!(payload.session_token(), Some("new_token"));
+	}
+
+	#[tokio::test]
+	async fn test_verify_handshake_token_signed_out() {
+		let helper = Helper::new();
+		let jwks = StaticJwksProvider::from_key(helper.jwks_key(KID));
+		let token = sign_handshake(&helper, serde_json::json!(["__session=; Path=/; Max-Age=0"]));
+
+		let payload = verify_handshake_token(&token, &jwks).await.expect("should be valid");
+		assert_eq!(payload.session_token(), None);
+	}
+
+	#[tokio::test]
+	async fn test_verify_handshake_token_invalid_signature() {
+		let jwks = StaticJwksProvider::from_key(Helper::new().jwks_key(KID));
+		let token = sign_handshake(&Helper::new(), serde_json::json!([]));
+
+		assert!(matches!(verify_handshake_token(&token, &jwks).await, Err(ClerkError::Unauthorized(_))));
+	}
+
+	#[tokio::test]
+	async fn test_verify_handshake_token_requires_exp() {
+		let helper = Helper::new();
+		let jwks = StaticJwksProvider::from_key(helper.jwks_key(KID));
+		let token = helper.sign(Some(KID), &serde_json::json!({ "handshake": ["__session=new_token; Path=/"] }));
+
+		assert!(matches!(verify_handshake_token(&token, &jwks).await, Err(ClerkError::Unauthorized(_))));
+	}
+}
@@ -0,0 +1,62 @@
+use crate::validators::{authorizer::Actor, routes::route_matches};
+use std::sync::Arc;
+
+/// Called with the actor and the subject of every impersonated request that passes the [`ImpersonationPolicy`], e.g. to
+/// write an audit log of who acted on behalf of which user.
+pub type ImpersonationHook = Arc<dyn Fn(&Actor, &str) + Send + Sync>;
+
+/// Whether impersonated sessions, whose tokens carry an `act` claim, are accepted.
+///
+/// Routes are patterns matched against the normalized path of the request, see [`route_matches`]. Requests whose path is
+/// unknown only match the global policies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ImpersonationPolicy {
+	#[default]
+	Allow,
+	Reject,
+	/// Reject impersonated sessions on these routes only, e.g. destructive endpoints.
+	RejectRoutes(Vec<String>),
+	/// Reject impersonated sessions everywhere but on these routes.
+	AllowRoutes(Vec<String>),
+}
+
+impl ImpersonationPolicy {
+	pub fn allows(&self, path: Option<&str>) -> bool {
+		let matches = |routes: &[String]| path.is_some_and(|path| routes.iter().any(|route| route_matches(route, path)));
+
+		match self {
+			ImpersonationPolicy::Allow => true,
+			ImpersonationPolicy::Reject => false,
+			ImpersonationPolicy::RejectRoutes(routes) => !matches(routes),
+			ImpersonationPolicy::AllowRoutes(routes) => matches(routes),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_impersonation_policy_routes() {
+		assert!(ImpersonationPolicy::Allow.allows(None));
+		assert!(!ImpersonationPolicy::Reject.allows(Some("/")));
+
+		let reject = ImpersonationPolicy::RejectRoutes(vec![String::from("/account/delete")]);
+		assert!(!reject.allows(Some("/account/delete")));
+		assert!(!reject.allows(Some("/account/%64elete")));
+		assert!(!reject.allows(Some("/account/delete/")));
+		assert!(!reject.allows(Some("//account/delete")));
+		assert!(reject.allows(Some("/account")));
+
+		let reject = ImpersonationPolicy::RejectRoutes(vec![String::from("/users/{id}/delete"), String::from("/admin/*")]);
+		assert!(!reject.allows(Some("/users/user_123/delete")));
+		assert!(!reject.allows(Some("/admin/users/user_123")));
+		assert!(reject.allows(Some("/users/user_123")));
+
+		let allow = ImpersonationPolicy::AllowRoutes(vec![String::from("/support")]);
+		assert!(allow.allows(Some("/support")));
+		assert!(!allow.allows(Some("/billing")));
+		assert!(!allow.allows(None));
+	}
+}
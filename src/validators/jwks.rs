@@ -1,28 +1,47 @@
 use crate::{
-	apis::jwks_api::{Jwks, JwksKey},
-	clerk::Clerk,
-	validators::authorizer::ClerkError,
+	apis::jwks_api::{Jwks, JwksKey, JwksModel},
+	clerk::{Clerk, USER_AGENT},
+	validators::{
+		authorizer::ClerkError,
+		publishable_key::{PublishableKey, PublishableKeyError},
+	},
 };
 use arc_swap::{ArcSwap, Guard};
 use async_trait::async_trait;
+use futures_util::future::{BoxFuture, FutureExt, Shared};
 use std::{
 	collections::HashMap,
-	sync::Arc,
+	sync::{Arc, Mutex, Weak},
 	time::{Duration, SystemTime},
 };
+use tokio::task::JoinHandle;
+
+/// The future returned by [`JwksProvider::get_instance_key`].
+pub type InstanceKeyFuture<'a, E> = BoxFuture<'a, Result<(JwksKey, Option<String>), E>>;
 
 /// Trait that implements a provider for the JWKS keys, to be used when validating a JWT.
 ///
-/// This crate provides two basic implementations of this trait, [`MemoryCacheJwksProvider`] and [`SimpleJwksProvider`].
+/// This crate provides a few implementations of this trait, such as [`MemoryCacheJwksProvider`], [`BackgroundRefreshJwksProvider`]
+/// and [`JwksProviderNoCache`].
 /// By implementing `get_key` for your own struct you can customize how the validator fetches keys.
 #[async_trait]
 pub trait JwksProvider {
 	type Error: Into<ClerkError>;
 
 	async fn get_key(&self, kid: &str) -> Result<JwksKey, Self::Error>;
+
+	/// Returns the key for `kid`, along with the name of the Clerk instance it belongs to if the provider knows it.
+	///
+	/// `issuer` is the not yet verified `iss` claim of the token being validated, which providers serving several
+	/// instances (like [`CompositeJwksProvider`]) can use for routing. The default implementation ignores it.
+	fn get_instance_key<'a>(&'a self, kid: &'a str, issuer: Option<&'a str>) -> InstanceKeyFuture<'a, Self::Error> {
+		let _ = issuer;
+		// only holds the future of `get_key`, so callers don't need a `Sync` bound on the provider
+		self.get_key(kid).map(|key| key.map(|key| (key, None))).boxed()
+	}
 }
 
-/// Error type used by [`MemoryCacheJwksProvider`] and [`SimpleJwksProvider`].
+/// Error type used by the [`JwksProvider`] implementations of this crate.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JwksProviderError {
 	UnknownKey,
@@ -38,16 +57,70 @@ impl From<JwksProviderError> for ClerkError {
 	}
 }
 
+/// Where a [`JwksProvider`] fetches the JWKS from.
+#[derive(Clone)]
+pub enum JwksSource {
+	/// The Backend API's `/jwks` endpoint, which requires a [`Clerk`] client configured with the secret key.
+	BackendApi(Clerk),
+	/// Any public JWKS url, e.g. the Frontend API's `https://<frontend-api>/.well-known/jwks.json`. No secret key is needed.
+	Url { client: reqwest::Client, url: String },
+}
+
+impl JwksSource {
+	/// Fetches the JWKS from the given url.
+	pub fn from_url(url: impl Into<String>) -> Self {
+		let client = reqwest::Client::builder()
+			.user_agent(USER_AGENT)
+			.build()
+			.expect("Error: could not initialize JWKS client. Please try again!");
+
+		Self::Url { client, url: url.into() }
+	}
+
+	/// Fetches the JWKS from the Frontend API encoded in the given publishable key (`pk_test_…` or `pk_live_…`).
+	pub fn from_publishable_key(publishable_key: &str) -> Result<Self, PublishableKeyError> {
+		let key = PublishableKey::parse(publishable_key)?;
+
+		Ok(Self::from_url(key.jwks_url()))
+	}
+
+	pub(crate) async fn fetch(&self) -> Result<JwksModel, JwksProviderError> {
+		match self {
+			JwksSource::BackendApi(clerk_client) => Jwks::get_jwks(clerk_client).await.map_err(|_| JwksProviderError::JwksApi),
+			JwksSource::Url { client, url } => {
+				let response = client.get(url).send().await.map_err(|_| JwksProviderError::JwksApi)?;
+
+				if !response.status().is_success() {
+					return Err(JwksProviderError::JwksApi);
+				}
+
+				response.json::<JwksModel>().await.map_err(|_| JwksProviderError::JwksApi)
+			}
+		}
+	}
+}
+
+impl From<Clerk> for JwksSource {
+	fn from(clerk_client: Clerk) -> Self {
+		JwksSource::BackendApi(clerk_client)
+	}
+}
+
 /// A [`JwksProvider`] implementation that doesn't do any caching.
 ///
 /// The JWKS is fetched from the Clerk API on every request.
 pub struct JwksProviderNoCache {
-	clerk_client: Clerk,
+	source: JwksSource,
 }
 
 impl JwksProviderNoCache {
 	pub fn new(clerk_client: Clerk) -> Self {
-		Self { clerk_client }
+		Self::from_source(clerk_client.into())
+	}
+
+	/// Creates a new [`JwksProviderNoCache`] that fetches the JWKS from the given source.
+	pub fn from_source(source: JwksSource) -> Self {
+		Self { source }
 	}
 }
 
@@ -56,7 +129,7 @@ impl JwksProvider for JwksProviderNoCache {
 	type Error = JwksProviderError;
 
 	async fn get_key(&self, kid: &str) -> Result<JwksKey, JwksProviderError> {
-		let jwks = Jwks::get_jwks(&self.clerk_client).await.map_err(|_| JwksProviderError::JwksApi)?;
+		let jwks = self.source.fetch().await?;
 
 		jwks.keys.into_iter().find(|k| k.kid == kid).ok_or(JwksProviderError::UnknownKey)
 	}
@@ -127,7 +200,7 @@ impl MemoryCacheJwksProviderState {
 /// The JWKS is fetched from the Clerk API on the first request or when the cache expires.
 /// Cache behavior can be configured with [`MemoryCacheJwksProviderOptions`].
 pub struct MemoryCacheJwksProvider {
-	clerk_client: Clerk,
+	source: JwksSource,
 	options: MemoryCacheJwksProviderOptions,
 	state: ArcSwap<MemoryCacheJwksProviderState>,
 }
@@ -140,13 +213,23 @@ impl MemoryCacheJwksProvider {
 
 	/// Creates a new [`MemoryCacheJwksProvider`] with the given client and options.
 	pub fn new_with_options(clerk_client: Clerk, options: MemoryCacheJwksProviderOptions) -> Self {
+		Self::from_source_with_options(clerk_client.into(), options)
+	}
+
+	/// Creates a new [`MemoryCacheJwksProvider`] that fetches the JWKS from the given source, with the default options.
+	pub fn from_source(source: JwksSource) -> Self {
+		Self::from_source_with_options(source, MemoryCacheJwksProviderOptions::default())
+	}
+
+	/// Creates a new [`MemoryCacheJwksProvider`] that fetches the JWKS from the given source, with the given options.
+	pub fn from_source_with_options(source: JwksSource, options: MemoryCacheJwksProviderOptions) -> Self {
 		let initial_state = MemoryCacheJwksProviderState {
 			keys: HashMap::new(),
 			last_updated: SystemTime::UNIX_EPOCH, // mark uninitialized
 		};
 
 		Self {
-			clerk_client,
+			source,
 			options,
 			state: ArcSwap::new(Arc::new(initial_state)),
 		}
@@ -154,7 +237,7 @@ impl MemoryCacheJwksProvider {
 
 	async fn refresh(&self) -> Result<Arc<MemoryCacheJwksProviderState>, JwksProviderError> {
 		// fetch jwks from clerk api
-		let jwks_model = Jwks::get_jwks(&self.clerk_client).await.map_err(|_| JwksProviderError::JwksApi)?;
+		let jwks_model = self.source.fetch().await?;
 
 		// construct new state
 		let keys = jwks_model.keys.into_iter().map(|k| (k.kid.clone(), k)).collect();
@@ -222,6 +305,359 @@ impl JwksProvider for MemoryCacheJwksProvider {
 	}
 }
 
+/// Options for [`BackgroundRefreshJwksProvider`].
+pub struct BackgroundRefreshJwksProviderOptions {
+	/// How often the JWKS is refreshed by the background task.
+	///
+	/// Defaults to 50 minutes, so keys are replaced well before the hour-long expiry used by [`MemoryCacheJwksProvider`].
+	pub refresh_interval: Duration,
+	/// How long the background task waits before retrying after a failed refresh.
+	///
+	/// Defaults to 30 seconds.
+	pub retry_interval: Duration,
+	/// How old the cached JWKS may get before requests stop being served from it.
+	/// While refreshes keep failing, stale keys are served until they reach this age. If this is None, stale keys are served forever.
+	///
+	/// Defaults to 24 hours.
+	pub max_staleness: Option<Duration>,
+	/// Configures the behavior of the provider when a `kid` that isn't in the cache is requested.
+	///
+	/// Defaults to refreshing when an unknown `kid` is requested, at most every 5 minutes.
+	pub refresh_on_unknown: RefreshOnUnknown,
+}
+
+impl Default for BackgroundRefreshJwksProviderOptions {
+	fn default() -> Self {
+		Self {
+			// 50 minutes
+			refresh_interval: Duration::from_secs(60 * 50),
+			// 30 seconds
+			retry_interval: Duration::from_secs(30),
+			// 24 hours
+			max_staleness: Some(Duration::from_secs(60 * 60 * 24)),
+			// 5 minutes
+			refresh_on_unknown: RefreshOnUnknown::Ratelimit(Duration::from_secs(60 * 5)),
+		}
+	}
+}
+
+type RefreshFuture = Shared<BoxFuture<'static, Result<Arc<MemoryCacheJwksProviderState>, JwksProviderError>>>;
+
+// Shared between BackgroundRefreshJwksProvider and its background task.
+struct BackgroundRefreshShared {
+	source: JwksSource,
+	options: BackgroundRefreshJwksProviderOptions,
+	state: ArcSwap<MemoryCacheJwksProviderState>,
+	// the refresh currently in flight, if any, so concurrent callers can await the same request
+	in_flight: Mutex<Option<RefreshFuture>>,
+}
+
+impl BackgroundRefreshShared {
+	/// Starts a refresh, or joins the one that is already in flight.
+	fn refresh(self: &Arc<Self>) -> RefreshFuture {
+		let mut in_flight = self.in_flight.lock().expect("jwks refresh lock poisoned");
+		if let Some(refresh) = in_flight.as_ref() {
+			return refresh.clone();
+		}
+
+		let shared = self.clone();
+		let refresh = async move {
+			let result = shared.source.fetch().await.map(|jwks_model| {
+				let keys = jwks_model.keys.into_iter().map(|k| (k.kid.clone(), k)).collect();
+				let state = Arc::new(MemoryCacheJwksProviderState {
+					keys,
+					last_updated: SystemTime::now(),
+				});
+				shared.state.store(state.clone());
+				state
+			});
+
+			*shared.in_flight.lock().expect("jwks refresh lock poisoned") = None;
+			result
+		}
+		.boxed()
+		.shared();
+
+		*in_flight = Some(refresh.clone());
+		refresh
+	}
+
+	/// Starts a refresh without waiting for it. The refresh is driven by its own task so it
+	/// completes even if the request that triggered it goes away.
+	///
+	/// Without a tokio runtime to spawn that task on, the refresh is awaited instead, returning
+	/// the refreshed keys if it succeeded.
+	async fn refresh_in_background(self: &Arc<Self>) -> Option<Arc<MemoryCacheJwksProviderState>> {
+		let refresh = self.refresh();
+		match tokio::runtime::Handle::try_current() {
+			Ok(runtime) => {
+				runtime.spawn(async move {
+					let _ = refresh.await;
+				});
+				None
+			}
+			Err(_) => refresh.await.ok(),
+		}
+	}
+}
+
+// Refreshes the JWKS on a fixed interval until the provider is dropped.
+async fn background_refresh_loop(shared: Weak<BackgroundRefreshShared>) {
+	loop {
+		let Some(strong) = shared.upgrade() else { return };
+
+		let delay = match strong.refresh().await {
+			Ok(_) => strong.options.refresh_interval,
+			Err(_) => {
+				log::warn!(
+					"clerk-rs: background JWKS refresh failed, retrying in {:?}",
+					strong.options.retry_interval
+				);
+				strong.options.retry_interval
+			}
+		};
+
+		// don't keep the provider alive while sleeping
+		drop(strong);
+		tokio::time::sleep(delay).await;
+	}
+}
+
+/// A [`JwksProvider`] implementation that keeps its in-memory cache fresh from a background task.
+///
+/// Requests are never blocked by a refresh once the cache has been populated: keys are refreshed ahead of time,
+/// concurrent refreshes are deduplicated, and stale keys keep being served while a refresh is in flight or the
+/// Clerk API is unreachable, up to [`BackgroundRefreshJwksProviderOptions::max_staleness`].
+///
+/// The background task is spawned on the current tokio runtime when the provider is created, and stops when the
+/// provider is dropped. If no runtime is running at that point, the cache is only refreshed when requests need it.
+pub struct BackgroundRefreshJwksProvider {
+	shared: Arc<BackgroundRefreshShared>,
+	task: Option<JoinHandle<()>>,
+}
+
+impl BackgroundRefreshJwksProvider {
+	/// Creates a new [`BackgroundRefreshJwksProvider`] with the given client and the default options.
+	pub fn new(clerk_client: Clerk) -> Self {
+		Self::new_with_options(clerk_client, BackgroundRefreshJwksProviderOptions::default())
+	}
+
+	/// Creates a new [`BackgroundRefreshJwksProvider`] with the given client and options.
+	pub fn new_with_options(clerk_client: Clerk, options: BackgroundRefreshJwksProviderOptions) -> Self {
+		Self::from_source_with_options(clerk_client.into(), options)
+	}
+
+	/// Creates a new [`BackgroundRefreshJwksProvider`] that fetches the JWKS from the given source, with the default options.
+	pub fn from_source(source: JwksSource) -> Self {
+		Self::from_source_with_options(source, BackgroundRefreshJwksProviderOptions::default())
+	}
+
+	/// Creates a new [`BackgroundRefreshJwksProvider`] that fetches the JWKS from the given source, with the given options.
+	pub fn from_source_with_options(source: JwksSource, options: BackgroundRefreshJwksProviderOptions) -> Self {
+		let initial_state = MemoryCacheJwksProviderState {
+			keys: HashMap::new(),
+			last_updated: SystemTime::UNIX_EPOCH, // mark uninitialized
+		};
+
+		let shared = Arc::new(BackgroundRefreshShared {
+			source,
+			options,
+			state: ArcSwap::new(Arc::new(initial_state)),
+			in_flight: Mutex::new(None),
+		});
+
+		let task = tokio::runtime::Handle::try_current()
+			.ok()
+			.map(|runtime| runtime.spawn(background_refresh_loop(Arc::downgrade(&shared))));
+
+		Self { shared, task }
+	}
+}
+
+impl Drop for BackgroundRefreshJwksProvider {
+	fn drop(&mut self) {
+		if let Some(task) = self.task.take() {
+			task.abort();
+		}
+	}
+}
+
+#[async_trait]
+impl JwksProvider for BackgroundRefreshJwksProvider {
+	type Error = JwksProviderError;
+
+	async fn get_key(&self, kid: &str) -> Result<JwksKey, Self::Error> {
+		let options = &self.shared.options;
+		let mut state = self.shared.state.load_full();
+		let mut refreshed = false;
+
+		if state.is_uninitialized() || state.is_expired(options.max_staleness) {
+			// there is nothing we are allowed to serve, so we have to wait for the refresh
+			state = self.shared.refresh().await?;
+			refreshed = true;
+		} else if state.is_expired(Some(options.refresh_interval)) {
+			// the background task is late (or failing), serve the stale keys and nudge a refresh
+			if let Some(refreshed_state) = self.shared.refresh_in_background().await {
+				state = refreshed_state;
+				refreshed = true;
+			}
+		}
+
+		if let Some(key) = state.keys.get(kid) {
+			return Ok(key.clone());
+		}
+
+		// key not in cache, do stuff depending on refresh_on_unknown
+		if refreshed {
+			return Err(JwksProviderError::UnknownKey);
+		}
+
+		match options.refresh_on_unknown {
+			RefreshOnUnknown::Never => return Err(JwksProviderError::UnknownKey),
+			RefreshOnUnknown::Ratelimit(min_age) if !state.is_expired(Some(min_age)) => return Err(JwksProviderError::UnknownKey),
+			_ => {}
+		}
+
+		let new_state = self.shared.refresh().await?;
+		new_state.keys.get(kid).cloned().ok_or(JwksProviderError::UnknownKey)
+	}
+}
+
+// Object safe version of JwksProvider, so CompositeJwksProvider can hold providers of different types.
+#[async_trait]
+trait DynJwksProvider: Send + Sync {
+	async fn get_dyn_key(&self, kid: &str) -> Result<JwksKey, ClerkError>;
+}
+
+#[async_trait]
+impl<J: JwksProvider + Send + Sync> DynJwksProvider for J {
+	async fn get_dyn_key(&self, kid: &str) -> Result<JwksKey, ClerkError> {
+		self.get_key(kid).await.map_err(Into::into)
+	}
+}
+
+/// A Clerk instance served by a [`CompositeJwksProvider`].
+pub struct JwksInstance {
+	name: String,
+	issuer: Option<String>,
+	provider: Box<dyn DynJwksProvider>,
+}
+
+impl JwksInstance {
+	/// Creates an instance with the given name, which is reported in [`ClerkJwt::instance`](crate::validators::authorizer::ClerkJwt::instance)
+	/// for tokens verified with its keys.
+	pub fn new<J: JwksProvider + Send + Sync + 'static>(name: impl Into<String>, jwks_provider: J) -> Self {
+		Self {
+			name: name.into(),
+			issuer: None,
+			provider: Box::new(jwks_provider),
+		}
+	}
+
+	/// Creates an instance whose keys are fetched from the Frontend API encoded in the given publishable key,
+	/// with the Frontend API url as its issuer.
+	pub fn from_publishable_key(name: impl Into<String>, publishable_key: &str) -> Result<Self, PublishableKeyError> {
+		let key = PublishableKey::parse(publishable_key)?;
+		let jwks_provider = MemoryCacheJwksProvider::from_source(JwksSource::from_url(key.jwks_url()));
+
+		Ok(Self::new(name, jwks_provider).with_issuer(key.frontend_api_url()))
+	}
+
+	/// Sets the `iss` claim of the tokens issued by this instance, used by [`CompositeStrategy::ByIssuer`].
+	pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+		self.issuer = Some(issuer.into());
+		self
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+}
+
+/// Configures how [`CompositeJwksProvider`] picks the instance to get a key from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompositeStrategy {
+	/// Query the instances in order, until one of them knows the requested `kid`.
+	Sequential,
+	/// Only query the instance whose issuer matches the token's `iss` claim.
+	/// Tokens from an issuer that doesn't match any instance are rejected.
+	ByIssuer,
+}
+
+/// A [`JwksProvider`] that combines the keys of several Clerk instances, e.g. to accept both staging and production
+/// tokens, or while migrating between instances.
+///
+/// Tokens validated through this provider are tagged with the name of the instance that verified them in
+/// [`ClerkJwt::instance`](crate::validators::authorizer::ClerkJwt::instance).
+///
+/// # Example
+/// ```
+/// let jwks_provider = CompositeJwksProvider::new(CompositeStrategy::ByIssuer)
+///     .with_instance(JwksInstance::new("production", MemoryCacheJwksProvider::new(production)).with_issuer("https://clerk.example.com"))
+///     .with_instance(JwksInstance::from_publishable_key("staging", "pk_test_...")?);
+/// ```
+pub struct CompositeJwksProvider {
+	instances: Vec<JwksInstance>,
+	strategy: CompositeStrategy,
+}
+
+impl CompositeJwksProvider {
+	/// Creates a provider without any instances, see [`CompositeJwksProvider::with_instance`].
+	pub fn new(strategy: CompositeStrategy) -> Self {
+		Self {
+			instances: Vec::new(),
+			strategy,
+		}
+	}
+
+	/// Adds an instance. With [`CompositeStrategy::Sequential`], instances are queried in the order they were added.
+	pub fn with_instance(mut self, instance: JwksInstance) -> Self {
+		self.instances.push(instance);
+		self
+	}
+
+	/// Returns the configured instances.
+	pub fn instances(&self) -> &[JwksInstance] {
+		&self.instances
+	}
+}
+
+#[async_trait]
+impl JwksProvider for CompositeJwksProvider {
+	type Error = JwksProviderError;
+
+	async fn get_key(&self, kid: &str) -> Result<JwksKey, Self::Error> {
+		self.get_instance_key(kid, None).await.map(|(key, _)| key)
+	}
+
+	fn get_instance_key<'a>(&'a self, kid: &'a str, issuer: Option<&'a str>) -> InstanceKeyFuture<'a, Self::Error> {
+		async move {
+			let candidates: Vec<&JwksInstance> = match self.strategy {
+				CompositeStrategy::Sequential => self.instances.iter().collect(),
+				CompositeStrategy::ByIssuer => self
+					.instances
+					.iter()
+					.filter(|i| issuer.is_some() && i.issuer.as_deref() == issuer)
+					.collect(),
+			};
+
+			let mut error = JwksProviderError::UnknownKey;
+			for instance in candidates {
+				match instance.provider.get_dyn_key(kid).await {
+					Ok(key) => return Ok((key, Some(instance.name.clone()))),
+					// the key belongs to another instance, keep looking
+					Err(ClerkError::Unauthorized(_)) => {}
+					// remember that an instance couldn't be reached, so an outage isn't reported as an invalid token
+					Err(_) => error = JwksProviderError::JwksApi,
+				}
+			}
+
+			Err(error)
+		}
+		.boxed()
+	}
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
 	use super::*;
@@ -250,7 +686,7 @@ pub(crate) mod tests {
 		}
 	}
 
-	const MOCK_JWKS_BODY: &str = r#"{
+	pub(crate) const MOCK_JWKS_BODY: &str = r#"{
 		"keys": [{
 			"use": "sig",
 			"kty": "RSA",
@@ -260,7 +696,7 @@ pub(crate) mod tests {
 			"e": "AQAB"
 		}]
 	}"#;
-	const MOCK_KID: &str = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+	pub(crate) const MOCK_KID: &str = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
 
 	#[tokio::test]
 	async fn test_simple_jwks_provider_success() {
@@ -498,4 +934,222 @@ pub(crate) mod tests {
 		// api should have been called only 2 times
 		mock.assert_async().await;
 	}
+
+	#[tokio::test]
+	async fn test_background_refresh_jwks_provider_deduplicates() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/v1/jwks").expect(1).with_body(MOCK_JWKS_BODY).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+
+		let jwks = Arc::new(BackgroundRefreshJwksProvider::new(clerk));
+
+		// the background task and all of these requests race for the first refresh
+		let requests = (0..10).map(|_| {
+			let jwks = jwks.clone();
+			tokio::spawn(async move { jwks.get_key(MOCK_KID).await })
+		});
+		for request in requests.collect::<Vec<_>>() {
+			request.await.unwrap().expect("should retrieve key");
+		}
+
+		// api should have been called only once
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_background_refresh_jwks_provider_refreshes_in_background() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/jwks")
+			.expect_at_least(3)
+			.with_body(MOCK_JWKS_BODY)
+			.create_async()
+			.await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+
+		let _jwks = BackgroundRefreshJwksProvider::new_with_options(
+			clerk,
+			BackgroundRefreshJwksProviderOptions {
+				refresh_interval: Duration::from_secs(1),
+				..Default::default()
+			},
+		);
+
+		// no requests are made, the background task refreshes on its own
+		tokio::time::sleep(Duration::from_millis(2500)).await;
+
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_background_refresh_jwks_provider_serves_stale_keys() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/v1/jwks").with_body(MOCK_JWKS_BODY).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+
+		let jwks = BackgroundRefreshJwksProvider::new_with_options(
+			clerk,
+			BackgroundRefreshJwksProviderOptions {
+				refresh_interval: Duration::from_secs(1),
+				retry_interval: Duration::from_secs(1),
+				max_staleness: Some(Duration::from_secs(3)),
+				..Default::default()
+			},
+		);
+
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+
+		// make clerk unreachable
+		mock.remove_async().await;
+		server.mock("GET", "/v1/jwks").with_status(500).create_async().await;
+
+		// the cache is past its refresh interval but not too stale yet
+		tokio::time::sleep(Duration::from_millis(1500)).await;
+		jwks.get_key(MOCK_KID).await.expect("should serve stale key");
+
+		// once the cache is older than max_staleness, requests fail
+		tokio::time::sleep(Duration::from_secs(2)).await;
+		let res = jwks.get_key(MOCK_KID).await.expect_err("should fail");
+		assert_eq!(res, JwksProviderError::JwksApi);
+	}
+
+	#[tokio::test]
+	async fn test_memory_cache_jwks_provider_url_source() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/.well-known/jwks.json")
+			.match_header("authorization", mockito::Matcher::Missing)
+			.expect(1)
+			.with_body(MOCK_JWKS_BODY)
+			.create_async()
+			.await;
+
+		let source = JwksSource::from_url(format!("{}/.well-known/jwks.json", server.url()));
+		let jwks = MemoryCacheJwksProvider::from_source(source);
+
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+
+		// the public jwks was fetched once, without any credentials
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_url_source_error_status() {
+		let mut server = mockito::Server::new_async().await;
+		server.mock("GET", "/.well-known/jwks.json").with_status(404).create_async().await;
+
+		let source = JwksSource::from_url(format!("{}/.well-known/jwks.json", server.url()));
+		let jwks = JwksProviderNoCache::from_source(source);
+
+		let res = jwks.get_key(MOCK_KID).await.expect_err("should fail");
+		assert_eq!(res, JwksProviderError::JwksApi);
+	}
+
+	#[test]
+	fn test_source_from_publishable_key() {
+		match JwksSource::from_publishable_key("pk_live_Y2xlcmsuZXhhbXBsZS5jb20k").expect("should be valid") {
+			JwksSource::Url { url, .. } => assert_eq!(url, "https://clerk.example.com/.well-known/jwks.json"),
+			JwksSource::BackendApi(_) => panic!("should fetch from the frontend api"),
+		}
+
+		assert!(JwksSource::from_publishable_key("sk_live_secret").is_err());
+	}
+
+	fn mock_key(kid: &str) -> JwksKey {
+		JwksKey {
+			use_key: String::from("sig"),
+			kty: String::from("RSA"),
+			kid: kid.to_string(),
+			alg: String::from("RS256"),
+			n: String::new(),
+			e: String::new(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_composite_jwks_provider_sequential() {
+		let jwks = CompositeJwksProvider::new(CompositeStrategy::Sequential)
+			.with_instance(JwksInstance::new("production", StaticJwksProvider::from_key(mock_key("prod_kid"))))
+			.with_instance(JwksInstance::new("staging", StaticJwksProvider::from_key(mock_key("staging_kid"))));
+
+		let (key, instance) = jwks.get_instance_key("staging_kid", None).await.expect("should retrieve key");
+		assert_eq!(key.kid, "staging_kid");
+		assert_eq!(instance.as_deref(), Some("staging"));
+
+		let (_, instance) = jwks
+			.get_instance_key("prod_kid", Some("https://ignored"))
+			.await
+			.expect("should retrieve key");
+		assert_eq!(instance.as_deref(), Some("production"));
+
+		let res = jwks.get_key("unknown key").await.expect_err("should fail");
+		assert_eq!(res, JwksProviderError::UnknownKey);
+	}
+
+	#[tokio::test]
+	async fn test_composite_jwks_provider_by_issuer() {
+		let jwks = CompositeJwksProvider::new(CompositeStrategy::ByIssuer)
+			.with_instance(
+				JwksInstance::new("production", StaticJwksProvider::from_key(mock_key("prod_kid"))).with_issuer("https://clerk.example.com"),
+			)
+			.with_instance(
+				JwksInstance::new("staging", StaticJwksProvider::from_key(mock_key("staging_kid"))).with_issuer("https://staging.clerk.accounts.dev"),
+			);
+
+		let (_, instance) = jwks
+			.get_instance_key("staging_kid", Some("https://staging.clerk.accounts.dev"))
+			.await
+			.expect("should retrieve key");
+		assert_eq!(instance.as_deref(), Some("staging"));
+
+		// a production kid presented with the staging issuer is not looked up in production
+		let res = jwks
+			.get_instance_key("prod_kid", Some("https://staging.clerk.accounts.dev"))
+			.await
+			.expect_err("should fail");
+		assert_eq!(res, JwksProviderError::UnknownKey);
+
+		// unknown and missing issuers are rejected
+		assert!(jwks.get_instance_key("prod_kid", Some("https://evil.example.com")).await.is_err());
+		assert!(jwks.get_key("prod_kid").await.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_composite_jwks_provider_unreachable_instance() {
+		let mut server = mockito::Server::new_async().await;
+		server.mock("GET", "/v1/jwks").with_status(500).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+
+		let jwks = CompositeJwksProvider::new(CompositeStrategy::Sequential)
+			.with_instance(JwksInstance::new("production", JwksProviderNoCache::new(Clerk::new(config))))
+			.with_instance(JwksInstance::new("staging", StaticJwksProvider::from_key(mock_key("staging_kid"))));
+
+		// the other instances are still queried
+		let (_, instance) = jwks.get_instance_key("staging_kid", None).await.expect("should retrieve key");
+		assert_eq!(instance.as_deref(), Some("staging"));
+
+		// but a kid that no reachable instance knows is reported as an api error
+		let res = jwks.get_key("unknown key").await.expect_err("should fail");
+		assert_eq!(res, JwksProviderError::JwksApi);
+	}
 }
@@ -0,0 +1,364 @@
+use crate::{
+	apis::jwks_api::JwksKey,
+	clerk::Clerk,
+	validators::jwks::{JwksProvider, JwksProviderError, JwksSource, MemoryCacheJwksProviderOptions, RefreshOnUnknown},
+};
+use arc_swap::ArcSwapOption;
+use async_trait::async_trait;
+use std::{
+	convert::Infallible,
+	fmt, io,
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+// numbers the temp files of FileJwksCache, so concurrent stores within a process don't write to the same one
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A snapshot of the JWKS, as stored in a [`JwksCache`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedJwks {
+	pub keys: Vec<JwksKey>,
+	/// Unix timestamp (in seconds) of when the keys were fetched from Clerk.
+	pub fetched_at: u64,
+}
+
+impl CachedJwks {
+	/// Creates a snapshot of the given keys, fetched just now.
+	pub fn new(keys: Vec<JwksKey>) -> Self {
+		let fetched_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+
+		Self { keys, fetched_at }
+	}
+
+	/// Returns the key with the given `kid`, if the snapshot contains it.
+	pub fn get_key(&self, kid: &str) -> Option<&JwksKey> {
+		self.keys.iter().find(|k| k.kid == kid)
+	}
+
+	fn is_expired(&self, expire_after: Option<Duration>) -> bool {
+		// if expire_after is None, the snapshot is never expired
+		let Some(expire_after) = expire_after else { return false };
+
+		let fetched_at = UNIX_EPOCH + Duration::from_secs(self.fetched_at);
+		let Ok(elapsed) = fetched_at.elapsed() else {
+			// fetched in the future according to our clock, treat it as expired
+			return true;
+		};
+
+		elapsed >= expire_after
+	}
+}
+
+/// Storage backend for [`CachedJwksProvider`].
+///
+/// Implement this trait to share the JWKS between processes, e.g. through a file, Redis or memcached, so short-lived
+/// workers don't have to fetch the keys from Clerk on startup. Errors are not fatal: a failed `load` is treated as a
+/// cache miss and a failed `store` is logged and ignored.
+#[async_trait]
+pub trait JwksCache {
+	type Error: fmt::Display;
+
+	/// Loads the cached JWKS, returning `None` if nothing has been stored yet.
+	async fn load(&self) -> Result<Option<CachedJwks>, Self::Error>;
+
+	/// Stores a freshly fetched JWKS, replacing the previous one.
+	async fn store(&self, jwks: &CachedJwks) -> Result<(), Self::Error>;
+}
+
+/// A [`JwksCache`] that keeps the JWKS in the memory of the current process.
+#[derive(Default)]
+pub struct MemoryJwksCache {
+	jwks: ArcSwapOption<CachedJwks>,
+}
+
+impl MemoryJwksCache {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl JwksCache for MemoryJwksCache {
+	type Error = Infallible;
+
+	async fn load(&self) -> Result<Option<CachedJwks>, Self::Error> {
+		Ok(self.jwks.load_full().map(|jwks| jwks.as_ref().clone()))
+	}
+
+	async fn store(&self, jwks: &CachedJwks) -> Result<(), Self::Error> {
+		self.jwks.store(Some(Arc::new(jwks.clone())));
+		Ok(())
+	}
+}
+
+/// A [`JwksCache`] that stores the JWKS as a JSON file on disk.
+///
+/// Writes go through a temporary file that is renamed into place, so processes sharing the file never read a partial write.
+pub struct FileJwksCache {
+	path: PathBuf,
+}
+
+impl FileJwksCache {
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into() }
+	}
+}
+
+#[async_trait]
+impl JwksCache for FileJwksCache {
+	type Error = io::Error;
+
+	async fn load(&self) -> Result<Option<CachedJwks>, Self::Error> {
+		let contents = match tokio::fs::read(&self.path).await {
+			Ok(contents) => contents,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(e),
+		};
+
+		serde_json::from_slice(&contents).map(Some).map_err(io::Error::from)
+	}
+
+	async fn store(&self, jwks: &CachedJwks) -> Result<(), Self::Error> {
+		let contents = serde_json::to_vec(jwks).map_err(io::Error::from)?;
+
+		let tmp_id = TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+		let mut tmp_path = self.path.clone().into_os_string();
+		tmp_path.push(format!(".{}.{}.tmp", std::process::id(), tmp_id));
+
+		tokio::fs::write(&tmp_path, contents).await?;
+		tokio::fs::rename(&tmp_path, &self.path).await
+	}
+}
+
+/// A [`JwksProvider`] implementation that caches keys in a pluggable [`JwksCache`].
+///
+/// The JWKS is fetched from the Clerk API when the cache is empty or expired, and written back to the cache.
+/// Cache behavior is configured with [`MemoryCacheJwksProviderOptions`], with the same semantics as [`MemoryCacheJwksProvider`](crate::validators::jwks::MemoryCacheJwksProvider).
+pub struct CachedJwksProvider<S> {
+	source: JwksSource,
+	cache: S,
+	options: MemoryCacheJwksProviderOptions,
+}
+
+impl<S: JwksCache> CachedJwksProvider<S> {
+	/// Creates a new [`CachedJwksProvider`] with the given client, cache and the default options.
+	pub fn new(clerk_client: Clerk, cache: S) -> Self {
+		Self::new_with_options(clerk_client, cache, MemoryCacheJwksProviderOptions::default())
+	}
+
+	/// Creates a new [`CachedJwksProvider`] with the given client, cache and options.
+	pub fn new_with_options(clerk_client: Clerk, cache: S, options: MemoryCacheJwksProviderOptions) -> Self {
+		Self::from_source_with_options(clerk_client.into(), cache, options)
+	}
+
+	/// Creates a new [`CachedJwksProvider`] that fetches the JWKS from the given source, with the default options.
+	pub fn from_source(source: JwksSource, cache: S) -> Self {
+		Self::from_source_with_options(source, cache, MemoryCacheJwksProviderOptions::default())
+	}
+
+	/// Creates a new [`CachedJwksProvider`] that fetches the JWKS from the given source, with the given options.
+	pub fn from_source_with_options(source: JwksSource, cache: S, options: MemoryCacheJwksProviderOptions) -> Self {
+		Self { source, cache, options }
+	}
+
+	/// Returns a reference to the underlying [`JwksCache`].
+	pub fn cache(&self) -> &S {
+		&self.cache
+	}
+
+	async fn load(&self) -> Option<CachedJwks> {
+		match self.cache.load().await {
+			Ok(jwks) => jwks,
+			Err(e) => {
+				log::warn!("clerk-rs: could not load cached JWKS: {}", e);
+				None
+			}
+		}
+	}
+
+	async fn refresh(&self) -> Result<CachedJwks, JwksProviderError> {
+		// fetch jwks from clerk api
+		let jwks_model = self.source.fetch().await?;
+		let jwks = CachedJwks::new(jwks_model.keys);
+
+		if let Err(e) = self.cache.store(&jwks).await {
+			log::warn!("clerk-rs: could not store JWKS in cache: {}", e);
+		}
+
+		Ok(jwks)
+	}
+}
+
+#[async_trait]
+impl<S: JwksCache + Send + Sync> JwksProvider for CachedJwksProvider<S> {
+	type Error = JwksProviderError;
+
+	async fn get_key(&self, kid: &str) -> Result<JwksKey, Self::Error> {
+		// if the cache is empty or expired, refresh it
+		let (jwks, refreshed) = match self.load().await {
+			Some(jwks) if !jwks.is_expired(self.options.expire_after) => (jwks, false),
+			_ => (self.refresh().await?, true),
+		};
+
+		if let Some(key) = jwks.get_key(kid) {
+			return Ok(key.clone());
+		}
+
+		// key not in cache, do stuff depending on refresh_on_unknown
+		if refreshed {
+			return Err(JwksProviderError::UnknownKey);
+		}
+
+		match self.options.refresh_on_unknown {
+			RefreshOnUnknown::Never => return Err(JwksProviderError::UnknownKey),
+			RefreshOnUnknown::Ratelimit(min_age) if !jwks.is_expired(Some(min_age)) => return Err(JwksProviderError::UnknownKey),
+			_ => {}
+		}
+
+		let jwks = self.refresh().await?;
+		jwks.get_key(kid).cloned().ok_or(JwksProviderError::UnknownKey)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		apis::jwks_api::JwksModel,
+		validators::jwks::tests::{MOCK_JWKS_BODY, MOCK_KID},
+		ClerkConfiguration,
+	};
+
+	fn temp_cache_path() -> PathBuf {
+		std::env::temp_dir().join(format!("clerk-rs-jwks-{}.json", rand::random::<u64>()))
+	}
+
+	#[tokio::test]
+	async fn test_cached_jwks_provider_memory_caching() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/v1/jwks").expect(1).with_body(MOCK_JWKS_BODY).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+
+		let jwks = CachedJwksProvider::new(clerk, MemoryJwksCache::new());
+
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+
+		// api should have been called only once
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_cached_jwks_provider_file_shared() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/v1/jwks").expect(1).with_body(MOCK_JWKS_BODY).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+		let path = temp_cache_path();
+
+		// the first "worker" fetches the jwks and writes it to disk
+		let first = CachedJwksProvider::new(clerk.clone(), FileJwksCache::new(&path));
+		first.get_key(MOCK_KID).await.expect("should retrieve key");
+
+		// the second one starts warm
+		let second = CachedJwksProvider::new(clerk, FileJwksCache::new(&path));
+		second.get_key(MOCK_KID).await.expect("should retrieve key");
+
+		std::fs::remove_file(&path).unwrap();
+
+		// api should have been called only once
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_cached_jwks_provider_expired() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/v1/jwks").expect(1).with_body(MOCK_JWKS_BODY).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+
+		// seed the cache with a snapshot that is older than expire_after
+		let cache = MemoryJwksCache::new();
+		let mut stale = CachedJwks::new(vec![]);
+		stale.fetched_at -= 60 * 60 * 2;
+		cache.store(&stale).await.unwrap();
+
+		let jwks = CachedJwksProvider::new(clerk, cache);
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+
+		// the refreshed snapshot was written back
+		let stored = jwks.cache().load().await.unwrap().expect("should be cached");
+		assert!(stored.get_key(MOCK_KID).is_some());
+
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_cached_jwks_provider_unknown_never() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/v1/jwks").expect(1).with_body(MOCK_JWKS_BODY).create_async().await;
+
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let clerk = Clerk::new(config);
+
+		let jwks = CachedJwksProvider::new_with_options(
+			clerk,
+			MemoryJwksCache::new(),
+			MemoryCacheJwksProviderOptions {
+				refresh_on_unknown: RefreshOnUnknown::Never,
+				..Default::default()
+			},
+		);
+
+		jwks.get_key(MOCK_KID).await.expect("should retrieve key");
+		let res = jwks.get_key("unknown key").await.expect_err("should fail");
+		assert_eq!(res, JwksProviderError::UnknownKey);
+
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_file_jwks_cache_concurrent_stores() {
+		let path = temp_cache_path();
+		let cache = FileJwksCache::new(&path);
+		let jwks = CachedJwks::new(serde_json::from_str::<JwksModel>(MOCK_JWKS_BODY).unwrap().keys);
+
+		let stores = (0..8).map(|_| cache.store(&jwks));
+		for result in futures_util::future::join_all(stores).await {
+			result.expect("concurrent stores should not clash");
+		}
+
+		let stored = cache.load().await.unwrap().expect("should be cached");
+		assert!(stored.get_key(MOCK_KID).is_some());
+		std::fs::remove_file(&path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_file_jwks_cache_missing_file() {
+		let cache = FileJwksCache::new(temp_cache_path());
+
+		assert!(cache.load().await.expect("missing file is not an error").is_none());
+	}
+}
@@ -1,5 +1,14 @@
 pub mod authorizer;
+pub mod handshake;
+pub mod impersonation;
 pub mod jwks;
+pub mod jwks_cache;
+pub mod publishable_key;
+pub mod request_state;
+pub mod reverification;
+pub mod routes;
+pub mod session_status;
+pub mod token_extractor;
 
 // Framework-specific modules
 // Validators for Rocket, etc coming very soon
@@ -11,3 +20,5 @@ pub mod axum;
 pub mod rocket;
 #[cfg(feature = "poem")]
 pub mod poem;
+#[cfg(feature = "tower")]
+pub mod tower;
@@ -1,11 +1,17 @@
 use poem::{
-	error::{InternalServerError, Unauthorized},
+	error::{Forbidden, InternalServerError, Unauthorized},
+	http::{header::HeaderName, HeaderValue, StatusCode},
 	Endpoint, Middleware, Request, Response, Result,
 };
 
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
 use super::{
-	authorizer::{ClerkAuthorizer, ClerkError, ClerkRequest},
+	authorizer::{AuthCheck, ClerkAuthorizer, ClerkError, ClerkJwt, ClerkRequest},
 	jwks::JwksProvider,
+	request_state::AuthStatus,
+	session_status::SessionStatusScope,
 };
 
 impl ClerkRequest for Request {
@@ -17,32 +23,59 @@ impl ClerkRequest for Request {
 		let jar = self.cookie();
 		jar.get(key).map(|c| c.value_str().to_string())
 	}
+
+	fn get_uri(&self) -> Option<String> {
+		self.uri().path_and_query().map(|val| val.to_string())
+	}
+
+	fn get_method(&self) -> Option<String> {
+		Some(self.method().to_string())
+	}
 }
 
 // The below implementation is derived from: https://docs.rs/poem/latest/poem/middleware/trait.Middleware.html#create-your-own-middleware
 
-pub struct ClerkPoemMiddleware<J> {
-	authorizer: ClerkAuthorizer<J>,
+pub struct ClerkPoemMiddleware<J, C = ()> {
+	authorizer: ClerkAuthorizer<J, C>,
 	exclude_routes: Option<Vec<String>>,
+	session_status: SessionStatusScope,
 }
 
 impl<J: JwksProvider> ClerkPoemMiddleware<J> {
 	pub fn new(jwks_provider: J, validate_session_cookie: bool, exclude_routes: Option<Vec<String>>) -> Self {
 		let authorizer = ClerkAuthorizer::new(jwks_provider, validate_session_cookie);
-		Self { authorizer, exclude_routes }
+		Self::from_authorizer(authorizer, exclude_routes)
+	}
+}
+
+impl<J: JwksProvider, C> ClerkPoemMiddleware<J, C> {
+	/// Creates the middleware from a configured authorizer, e.g. one using [`ClerkAuthorizer::with_request_state`].
+	pub fn from_authorizer(authorizer: ClerkAuthorizer<J, C>, exclude_routes: Option<Vec<String>>) -> Self {
+		Self {
+			authorizer,
+			exclude_routes,
+			session_status: SessionStatusScope::default(),
+		}
+	}
+
+	/// Selects the routes that also confirm the session is still active, see [`ClerkAuthorizer::with_session_status`].
+	pub fn with_session_status(mut self, scope: SessionStatusScope) -> Self {
+		self.session_status = scope;
+		self
 	}
 }
 
-impl<E: Endpoint, J: JwksProvider + Send + Sync> Middleware<E> for ClerkPoemMiddleware<J>
+impl<E: Endpoint, J: JwksProvider + Send + Sync, C: DeserializeOwned + Clone + Send + Sync + 'static> Middleware<E> for ClerkPoemMiddleware<J, C>
 where
 	E: poem::Endpoint<Output = Response>,
 {
-	type Output = ClerkPoemMiddlewareImpl<J, E>;
+	type Output = ClerkPoemMiddlewareImpl<J, E, C>;
 
 	fn transform(&self, ep: E) -> Self::Output {
 		Self::Output {
 			authorizer: self.authorizer.clone(),
 			exclude_routes: self.exclude_routes.clone(),
+			session_status: self.session_status.clone(),
 			ep,
 		}
 	}
@@ -51,14 +84,15 @@ where
 /// A middleware to authenticate Poem routes using Clerk.com.
 ///
 /// If auth succeeds, the JWT data is available using `Data<&ClerkJwt>` (or
-/// `req.data::<ClerkJwt>()`).
-pub struct ClerkPoemMiddlewareImpl<J, E> {
-	authorizer: ClerkAuthorizer<J>,
+/// `req.data::<ClerkJwt>()`), or `Data<&ClerkJwt<C>>` with the custom claims of the authorizer.
+pub struct ClerkPoemMiddlewareImpl<J, E, C = ()> {
+	authorizer: ClerkAuthorizer<J, C>,
 	exclude_routes: Option<Vec<String>>,
+	session_status: SessionStatusScope,
 	ep: E,
 }
 
-impl<E: Endpoint, J: JwksProvider + Send + Sync> Endpoint for ClerkPoemMiddlewareImpl<J, E>
+impl<E: Endpoint, J: JwksProvider + Send + Sync, C: DeserializeOwned + Clone + Send + Sync + 'static> Endpoint for ClerkPoemMiddlewareImpl<J, E, C>
 where
 	E: poem::Endpoint<Output = Response>,
 {
@@ -72,19 +106,122 @@ where
 			}
 		}
 
-		match self.authorizer.authorize(&req).await {
+		let verify_session_status = self.session_status.applies_to(req.uri().path());
+		let state = self.authorizer.authenticate_request_with_status(&req, verify_session_status).await;
+
+		// The browser has to refresh its session (respond with the redirect or interstitial page), or the refreshed cookies
+		// have to be set on the error response
+		if state.status == AuthStatus::Handshake || (!state.is_signed_in() && !state.headers.is_empty()) {
+			let mut response = Response::builder().status(StatusCode::from_u16(state.status_code()).unwrap_or(StatusCode::UNAUTHORIZED));
+			for (key, val) in &state.headers {
+				response = response.header(key.as_str(), val.as_str());
+			}
+
+			return Ok(response.body(state.response_body()));
+		}
+
+		let headers = state.headers.clone();
+		match state.into_result() {
 			Ok(jwt) => {
 				// This can be accessed using Data<&ClerkJwt>
 				req.set_data(jwt);
 
 				// call next
-				self.ep.call(req).await
+				let mut response = self.ep.call(req).await?;
+				// e.g. the cookies refreshed by a handshake
+				for (key, val) in headers {
+					if let (Ok(key), Ok(val)) = (HeaderName::try_from(key), HeaderValue::try_from(val)) {
+						response.headers_mut().append(key, val);
+					}
+				}
+				Ok(response)
 			}
-			Err(error) => match error {
-				// The error strings are passed through with the correct status code
-				ClerkError::Unauthorized(_) => Err(Unauthorized(error)),
-				ClerkError::InternalServerError(_) => Err(InternalServerError(error)),
-			},
+			Err(error) => Err(into_poem_error(error)),
 		}
 	}
 }
+
+/// A middleware that only lets requests through whose session passes an [`AuthCheck`], responding with a 403 otherwise.
+///
+/// It reads the jwt added by [`ClerkPoemMiddleware`], so it has to be applied after it:
+/// ```
+/// let app = Route::new()
+///     .at("/billing", get(billing).with(ClerkPoemAuthCheck::new(AuthCheck::permission("org:billing:manage"))))
+///     .with(clerk_poem_middleware);
+/// ```
+pub struct ClerkPoemAuthCheck<C = ()> {
+	check: AuthCheck,
+	_claims: PhantomData<fn() -> C>,
+}
+
+impl ClerkPoemAuthCheck {
+	pub fn new(check: AuthCheck) -> Self {
+		Self { check, _claims: PhantomData }
+	}
+}
+
+impl<C> ClerkPoemAuthCheck<C> {
+	/// Reads the jwt of a middleware using custom claims, see [`ClerkAuthorizer::with_claims`].
+	pub fn with_claims<T>(self) -> ClerkPoemAuthCheck<T> {
+		ClerkPoemAuthCheck {
+			check: self.check,
+			_claims: PhantomData,
+		}
+	}
+}
+
+impl<E: Endpoint, C: Send + Sync + 'static> Middleware<E> for ClerkPoemAuthCheck<C>
+where
+	E: poem::Endpoint<Output = Response>,
+{
+	type Output = ClerkPoemAuthCheckImpl<E, C>;
+
+	fn transform(&self, ep: E) -> Self::Output {
+		Self::Output {
+			check: self.check.clone(),
+			ep,
+			_claims: PhantomData,
+		}
+	}
+}
+
+pub struct ClerkPoemAuthCheckImpl<E, C = ()> {
+	check: AuthCheck,
+	ep: E,
+	_claims: PhantomData<fn() -> C>,
+}
+
+impl<E: Endpoint, C: Send + Sync + 'static> Endpoint for ClerkPoemAuthCheckImpl<E, C>
+where
+	E: poem::Endpoint<Output = Response>,
+{
+	type Output = Response;
+
+	async fn call(&self, req: Request) -> Result<Self::Output> {
+		let result = match req.data::<ClerkJwt<C>>() {
+			Some(jwt) => jwt.require(&self.check),
+			None => Err(ClerkError::Unauthorized(String::from("Error: The request is not signed in!"))),
+		};
+
+		match result {
+			Ok(()) => self.ep.call(req).await,
+			Err(error) => Err(into_poem_error(error)),
+		}
+	}
+}
+
+// The error strings are passed through with the correct status code
+fn into_poem_error(error: ClerkError) -> poem::Error {
+	match error {
+		ClerkError::Unauthorized(_) => Unauthorized(error),
+		ClerkError::Forbidden(_) => Forbidden(error),
+		// the frontend SDKs read the JSON body to prompt for the reverification
+		ClerkError::ReverificationRequired(_) => poem::Error::from_response(
+			Response::builder()
+				.status(StatusCode::FORBIDDEN)
+				.content_type("application/json")
+				.body(error.message()),
+		),
+		ClerkError::InternalServerError(_) => InternalServerError(error),
+	}
+}
@@ -0,0 +1,141 @@
+use base64::{
+	alphabet,
+	engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+	Engine,
+};
+use std::{error::Error, fmt, str::FromStr};
+
+// Publishable keys are standard base64, but may or may not be padded
+const PUBLISHABLE_KEY_ENGINE: GeneralPurpose = GeneralPurpose::new(
+	&alphabet::STANDARD,
+	GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// The type of Clerk instance a publishable key belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstanceType {
+	Development,
+	Production,
+}
+
+/// A decoded Clerk publishable key (`pk_test_…` or `pk_live_…`).
+///
+/// Publishable keys are safe to ship to clients, and encode the Frontend API host of the instance. That is enough to
+/// locate the instance's public JWKS, so services that only verify tokens don't need the secret key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublishableKey {
+	pub instance_type: InstanceType,
+	/// The Frontend API host, e.g. `clerk.example.com` or `happy-hippo-1.clerk.accounts.dev`.
+	pub frontend_api: String,
+}
+
+impl PublishableKey {
+	/// Decodes a publishable key.
+	pub fn parse(key: &str) -> Result<Self, PublishableKeyError> {
+		let (instance_type, encoded) = if let Some(encoded) = key.strip_prefix("pk_test_") {
+			(InstanceType::Development, encoded)
+		} else if let Some(encoded) = key.strip_prefix("pk_live_") {
+			(InstanceType::Production, encoded)
+		} else {
+			return Err(PublishableKeyError::InvalidPrefix);
+		};
+
+		let decoded = PUBLISHABLE_KEY_ENGINE.decode(encoded).map_err(|_| PublishableKeyError::InvalidEncoding)?;
+		let decoded = String::from_utf8(decoded).map_err(|_| PublishableKeyError::InvalidEncoding)?;
+
+		// the frontend api host is terminated by a `$`
+		match decoded.strip_suffix('$') {
+			Some(frontend_api) if !frontend_api.is_empty() && !frontend_api.contains('$') => Ok(Self {
+				instance_type,
+				frontend_api: frontend_api.to_string(),
+			}),
+			_ => Err(PublishableKeyError::InvalidEncoding),
+		}
+	}
+
+	/// The url of the instance's Frontend API.
+	pub fn frontend_api_url(&self) -> String {
+		format!("https://{}", self.frontend_api)
+	}
+
+	/// The url of the instance's public JSON Web Key Set.
+	pub fn jwks_url(&self) -> String {
+		format!("{}/.well-known/jwks.json", self.frontend_api_url())
+	}
+}
+
+impl FromStr for PublishableKey {
+	type Err = PublishableKeyError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse(s)
+	}
+}
+
+/// Error returned when decoding a [`PublishableKey`] fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PublishableKeyError {
+	/// The key doesn't start with `pk_test_` or `pk_live_`.
+	InvalidPrefix,
+	/// The rest of the key is not a base64 encoded Frontend API host.
+	InvalidEncoding,
+}
+
+impl fmt::Display for PublishableKeyError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			PublishableKeyError::InvalidPrefix => write!(f, "Invalid publishable key: expected a pk_test_ or pk_live_ prefix"),
+			PublishableKeyError::InvalidEncoding => write!(f, "Invalid publishable key: could not decode the Frontend API"),
+		}
+	}
+}
+
+impl Error for PublishableKeyError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_publishable_key() {
+		// base64("clerk.example.com$")
+		let key = PublishableKey::parse("pk_live_Y2xlcmsuZXhhbXBsZS5jb20k").expect("should be valid");
+
+		assert_eq!(key.instance_type, InstanceType::Production);
+		assert_eq!(key.frontend_api, "clerk.example.com");
+		assert_eq!(key.jwks_url(), "https://clerk.example.com/.well-known/jwks.json");
+	}
+
+	#[test]
+	fn test_parse_publishable_key_development() {
+		// base64("happy-hippo-1.clerk.accounts.dev$")
+		let key = PublishableKey::parse("pk_test_aGFwcHktaGlwcG8tMS5jbGVyay5hY2NvdW50cy5kZXYk").expect("should be valid");
+
+		assert_eq!(key.instance_type, InstanceType::Development);
+		assert_eq!(key.frontend_api, "happy-hippo-1.clerk.accounts.dev");
+	}
+
+	#[test]
+	fn test_parse_publishable_key_padding() {
+		// base64("clerk.acme.io$") with and without padding
+		let padded = PublishableKey::parse("pk_live_Y2xlcmsuYWNtZS5pbyQ=").expect("should be valid");
+		let unpadded = PublishableKey::parse("pk_live_Y2xlcmsuYWNtZS5pbyQ").expect("should be valid");
+
+		assert_eq!(padded.frontend_api, "clerk.acme.io");
+		assert_eq!(padded, unpadded);
+	}
+
+	#[test]
+	fn test_parse_publishable_key_invalid() {
+		assert_eq!(
+			PublishableKey::parse("sk_test_Y2xlcmsuZXhhbXBsZS5jb20k"),
+			Err(PublishableKeyError::InvalidPrefix)
+		);
+		assert_eq!(PublishableKey::parse("pk_test_!!!"), Err(PublishableKeyError::InvalidEncoding));
+		// base64("clerk.example.com"), missing the trailing `$`
+		assert_eq!(
+			PublishableKey::parse("pk_test_Y2xlcmsuZXhhbXBsZS5jb20"),
+			Err(PublishableKeyError::InvalidEncoding)
+		);
+	}
+}
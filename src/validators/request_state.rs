@@ -0,0 +1,995 @@
+use crate::{
+	apis::{miscellaneous_api::Miscellaneous, urlencode},
+	clerk::Clerk,
+	validators::{
+		authorizer::{get_unverified_claims, validate_jwt_with_claims, ClerkError, ClerkJwt, ClerkRequest},
+		handshake::{get_handshake_token, verify_handshake_token, HandshakeTokenSource, HANDSHAKE_PARAM},
+		jwks::JwksProvider,
+		publishable_key::{InstanceType, PublishableKey, PublishableKeyError},
+		token_extractor::{TokenExtractor, TokenSource},
+	},
+};
+use arc_swap::ArcSwapOption;
+use serde::de::DeserializeOwned;
+use std::{
+	sync::Arc,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+const SESSION_COOKIE: &str = "__session";
+const CLIENT_UAT_COOKIE: &str = "__client_uat";
+const REDIRECT_LOOP_COOKIE: &str = "__clerk_redirect_loop";
+const DEV_BROWSER_PARAM: &str = "__clerk_db_jwt";
+const SYNCED_PARAM: &str = "__clerk_synced";
+const REDIRECT_URL_PARAM: &str = "__clerk_redirect_url";
+
+/// How the authorizer lets the browser refresh a session that can't be verified from the request alone.
+#[derive(Clone)]
+pub enum HandshakeMode {
+	/// Redirect the browser to the Frontend API handshake endpoint, which refreshes the session cookies and redirects
+	/// back to the requested url.
+	Redirect,
+	/// Respond with the interstitial page, which loads clerk.js to refresh the session in the browser. The markup is
+	/// fetched once through the given client and reused for later requests.
+	Interstitial(Clerk),
+}
+
+/// Whether the app runs on the primary domain of the instance or on a satellite domain.
+///
+/// Satellite apps can't read the session cookies of the primary domain, so browsers without a session are sent to the
+/// primary to sync it, which sends them back with `__clerk_synced=true`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DomainMode {
+	/// The app runs on the primary domain. Development browsers syncing from one of the listed satellite domains (or
+	/// their subdomains) are sent back to them, redirects to any other domain are ignored.
+	Primary { satellite_domains: Vec<String> },
+	/// The app runs on the given satellite domain, e.g. `example-docs.com`.
+	///
+	/// Production instances sync through the satellite's Frontend API (`clerk.<domain>`). Development instances sync through
+	/// the primary's sign-in page, so `sign_in_url` has to be set for them.
+	Satellite { domain: String, sign_in_url: Option<String> },
+}
+
+impl Default for DomainMode {
+	fn default() -> Self {
+		DomainMode::Primary {
+			satellite_domains: Vec::new(),
+		}
+	}
+}
+
+/// Options for [`ClerkAuthorizer::with_request_state`](crate::validators::authorizer::ClerkAuthorizer::with_request_state).
+#[derive(Clone)]
+pub struct AuthenticateRequestOptions {
+	/// The publishable key of the instance, which locates the Frontend API used for handshakes.
+	pub publishable_key: PublishableKey,
+	pub handshake_mode: HandshakeMode,
+	/// How many handshakes a browser may go through in a row before the request is treated as signed out.
+	pub redirect_loop_limit: u32,
+	pub domain_mode: DomainMode,
+	/// Absolute url the Frontend API is proxied through, e.g. `https://example.com/__clerk`. It replaces the Frontend API
+	/// of the publishable key for handshakes.
+	pub proxy_url: Option<String>,
+}
+
+impl AuthenticateRequestOptions {
+	/// Creates options that redirect to the handshake endpoint of the given publishable key's instance.
+	pub fn new(publishable_key: &str) -> Result<Self, PublishableKeyError> {
+		Ok(Self {
+			publishable_key: PublishableKey::parse(publishable_key)?,
+			handshake_mode: HandshakeMode::Redirect,
+			redirect_loop_limit: 3,
+			domain_mode: DomainMode::default(),
+			proxy_url: None,
+		})
+	}
+
+	pub fn with_handshake_mode(mut self, handshake_mode: HandshakeMode) -> Self {
+		self.handshake_mode = handshake_mode;
+		self
+	}
+
+	pub fn with_domain_mode(mut self, domain_mode: DomainMode) -> Self {
+		self.domain_mode = domain_mode;
+		self
+	}
+
+	pub fn with_proxy_url(mut self, proxy_url: impl Into<String>) -> Self {
+		self.proxy_url = Some(proxy_url.into());
+		self
+	}
+
+	fn is_development(&self) -> bool {
+		self.publishable_key.instance_type == InstanceType::Development
+	}
+
+	/// The url of the Frontend API handshakes go through, taking the proxy and satellite domain into account.
+	pub fn frontend_api_url(&self) -> String {
+		match (&self.proxy_url, &self.domain_mode) {
+			(Some(proxy_url), _) => proxy_url.trim_end_matches('/').to_string(),
+			// production satellites have their own Frontend API on their domain
+			(None, DomainMode::Satellite { domain, .. }) if !self.is_development() => format!("https://clerk.{domain}"),
+			(None, _) => self.publishable_key.frontend_api_url(),
+		}
+	}
+}
+
+/// The outcome of authenticating a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthStatus {
+	SignedIn,
+	SignedOut,
+	/// The session has to be refreshed by the browser before the request can be authenticated.
+	Handshake,
+}
+
+/// Why a request ended up signed out or in a handshake. The string values match the ones used by the other Clerk SDKs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthReason {
+	SessionTokenAndUatMissing,
+	SessionTokenMissing,
+	SessionTokenWithoutClientUat,
+	SessionTokenExpired,
+	SessionTokenIatBeforeClientUat,
+	SessionTokenInvalid,
+	HeaderTokenInvalid,
+	RedirectLoop,
+	/// The handshake token was passed in the url, which the browser is redirected away from once the cookies are set.
+	HandshakeResolved,
+	SatelliteCookieNeedsSyncing,
+	PrimaryRespondsToSyncing,
+	DevBrowserSync,
+	/// The token is valid, but its session was revoked or ended since it was issued.
+	SessionNotActive,
+	/// The session is impersonated, which the impersonation policy doesn't allow on the route.
+	ImpersonationNotAllowed,
+}
+
+impl AuthReason {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			AuthReason::SessionTokenAndUatMissing => "session-token-and-uat-missing",
+			AuthReason::SessionTokenMissing => "client-uat-but-no-session-token",
+			AuthReason::SessionTokenWithoutClientUat => "session-token-but-no-client-uat",
+			AuthReason::SessionTokenExpired => "session-token-expired",
+			AuthReason::SessionTokenIatBeforeClientUat => "session-token-iat-before-client-uat",
+			AuthReason::SessionTokenInvalid => "session-token-invalid",
+			AuthReason::HeaderTokenInvalid => "header-token-invalid",
+			AuthReason::RedirectLoop => "redirect-loop",
+			AuthReason::HandshakeResolved => "handshake-resolved",
+			AuthReason::SatelliteCookieNeedsSyncing => "satellite-needs-syncing",
+			AuthReason::PrimaryRespondsToSyncing => "primary-responds-to-syncing",
+			AuthReason::DevBrowserSync => "dev-browser-sync",
+			AuthReason::SessionNotActive => "session-not-active",
+			AuthReason::ImpersonationNotAllowed => "impersonation-not-allowed",
+		}
+	}
+}
+
+/// The result of [`ClerkAuthorizer::authenticate_request`](crate::validators::authorizer::ClerkAuthorizer::authenticate_request).
+///
+/// Signed in requests carry the verified jwt. Other requests carry what the middleware should respond with: an error for
+/// signed out requests, or the redirect or interstitial page of a handshake.
+#[derive(Clone, Debug)]
+pub struct RequestState<C = ()> {
+	pub status: AuthStatus,
+	pub reason: Option<AuthReason>,
+	pub jwt: Option<ClerkJwt<C>>,
+	pub error: Option<ClerkError>,
+	/// Headers the response has to carry, e.g. the `Location` of a handshake redirect or the `Set-Cookie` directives of a
+	/// resolved handshake. These also apply to the response of signed in requests.
+	pub headers: Vec<(String, String)>,
+	/// Body of a handshake response, e.g. the interstitial markup.
+	pub body: Option<String>,
+}
+
+impl<C> RequestState<C> {
+	pub(crate) fn signed_in(jwt: ClerkJwt<C>) -> Self {
+		Self {
+			status: AuthStatus::SignedIn,
+			reason: None,
+			jwt: Some(jwt),
+			error: None,
+			headers: Vec::new(),
+			body: None,
+		}
+	}
+
+	pub(crate) fn signed_out(reason: Option<AuthReason>, error: ClerkError) -> Self {
+		Self {
+			status: AuthStatus::SignedOut,
+			reason,
+			jwt: None,
+			error: Some(error),
+			headers: Vec::new(),
+			body: None,
+		}
+	}
+
+	fn handshake(reason: AuthReason, headers: Vec<(String, String)>, body: Option<String>) -> Self {
+		Self {
+			status: AuthStatus::Handshake,
+			reason: Some(reason),
+			jwt: None,
+			error: None,
+			headers,
+			body,
+		}
+	}
+
+	pub(crate) fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+		self.headers = headers;
+		self
+	}
+
+	/// Copies the state without the jwt, for responders that don't know the claims type.
+	#[cfg(feature = "rocket")]
+	pub(crate) fn without_jwt(&self) -> RequestState {
+		RequestState {
+			status: self.status,
+			reason: self.reason,
+			jwt: None,
+			error: self.error.clone(),
+			headers: self.headers.clone(),
+			body: self.body.clone(),
+		}
+	}
+
+	pub fn is_signed_in(&self) -> bool {
+		self.status == AuthStatus::SignedIn
+	}
+
+	/// The status code to respond with when the request is not signed in.
+	pub fn status_code(&self) -> u16 {
+		match (self.status, &self.error) {
+			(AuthStatus::SignedIn, _) => 200,
+			(AuthStatus::SignedOut, Some(error)) => error.status_code(),
+			(AuthStatus::SignedOut, None) => 401,
+			// the interstitial page is served with a 401 so that it's never mistaken for the requested page
+			(AuthStatus::Handshake, _) if self.body.is_some() => 401,
+			(AuthStatus::Handshake, _) => 307,
+		}
+	}
+
+	/// The body to respond with when the request is not signed in.
+	pub fn response_body(&self) -> String {
+		match (&self.body, &self.error) {
+			(Some(body), _) => body.clone(),
+			(None, Some(error)) => error.message(),
+			(None, None) => String::new(),
+		}
+	}
+
+	/// Converts the state into the result of [`ClerkAuthorizer::authorize`](crate::validators::authorizer::ClerkAuthorizer::authorize).
+	pub fn into_result(self) -> Result<ClerkJwt<C>, ClerkError> {
+		match (self.jwt, self.error) {
+			(Some(jwt), _) => Ok(jwt),
+			(None, Some(error)) => Err(error),
+			(None, None) => Err(ClerkError::Unauthorized(String::from("Error: The session has to be refreshed!"))),
+		}
+	}
+}
+
+/// Implements the request authentication flow of the Clerk SDKs, where the `__session` cookie is checked against the
+/// `__client_uat` cookie to find out whether the browser holds an outdated session.
+pub(crate) struct RequestAuthenticator {
+	options: AuthenticateRequestOptions,
+	interstitial: ArcSwapOption<String>,
+}
+
+impl RequestAuthenticator {
+	pub(crate) fn new(options: AuthenticateRequestOptions) -> Self {
+		Self {
+			options,
+			interstitial: ArcSwapOption::empty(),
+		}
+	}
+
+	pub(crate) async fn authenticate<J: JwksProvider, T: ClerkRequest, C: DeserializeOwned>(
+		&self,
+		jwks: &Arc<J>,
+		extractors: &[TokenExtractor],
+		request: &T,
+	) -> RequestState<C> {
+		// tokens sent in the header (or any source other than the session cookie) come from clients that refresh them on
+		// their own, so they are only verified
+		let extracted = extractors
+			.iter()
+			.filter(|extractor| !extractor.is_session_cookie())
+			.find_map(|extractor| extractor.extract(request));
+		if let Some(extracted) = extracted {
+			return match validate_jwt_with_claims(&extracted.token, jwks.clone()).await {
+				Ok(mut jwt) => {
+					jwt.token_source = Some(extracted.source);
+					RequestState::signed_in(jwt)
+				}
+				Err(error) => RequestState::signed_out(Some(AuthReason::HeaderTokenInvalid), error),
+			};
+		}
+
+		// the browser is coming back from a handshake with refreshed cookies
+		if let Some(state) = self.resolve_handshake(jwks, request).await {
+			return state;
+		}
+
+		let session_token = request.get_cookie(SESSION_COOKIE);
+		let client_uat = request.get_cookie(CLIENT_UAT_COOKIE).and_then(|uat| uat.parse::<i64>().ok()).unwrap_or(0);
+
+		// the session has to be synced between the primary and satellite domains
+		if let Some(state) = self.sync(request, client_uat).await {
+			return state;
+		}
+
+		let Some(session_token) = session_token else {
+			if client_uat > 0 {
+				// the browser is signed in, but the short lived session cookie is gone
+				return self.handshake(request, AuthReason::SessionTokenMissing).await;
+			}
+
+			return RequestState::signed_out(
+				Some(AuthReason::SessionTokenAndUatMissing),
+				ClerkError::Unauthorized(String::from(
+					"Error: No Authorization header or session cookie found on the request payload!",
+				)),
+			);
+		};
+
+		if client_uat <= 0 {
+			// the browser signed out, but the session cookie is left over
+			return self.handshake(request, AuthReason::SessionTokenWithoutClientUat).await;
+		}
+
+		match validate_jwt_with_claims(&session_token, jwks.clone()).await {
+			// the client was updated (e.g. signed in to another account) after the token was issued
+			Ok(jwt) if (jwt.iat as i64) < client_uat => self.handshake(request, AuthReason::SessionTokenIatBeforeClientUat).await,
+			Ok(mut jwt) => {
+				jwt.token_source = Some(TokenSource::Cookie(SESSION_COOKIE.into()));
+				RequestState::signed_in(jwt)
+			}
+			Err(ClerkError::Unauthorized(_)) if is_expired(&session_token) => self.handshake(request, AuthReason::SessionTokenExpired).await,
+			Err(error) => RequestState::signed_out(Some(AuthReason::SessionTokenInvalid), error),
+		}
+	}
+
+	async fn resolve_handshake<J: JwksProvider, T: ClerkRequest, C: DeserializeOwned>(&self, jwks: &Arc<J>, request: &T) -> Option<RequestState<C>> {
+		let (token, source) = get_handshake_token(request)?;
+
+		let payload = match verify_handshake_token(&token, jwks.as_ref()).await {
+			Ok(payload) => payload,
+			Err(error) => {
+				// fall back to the cookies the browser sent, the redirect loop guard stops repeated handshakes
+				log::warn!("clerk-rs: unable to resolve handshake: {}", error);
+				return None;
+			}
+		};
+
+		let mut headers: Vec<(String, String)> = payload
+			.directives
+			.iter()
+			.map(|directive| (String::from("Set-Cookie"), directive.header.clone()))
+			.collect();
+
+		match source {
+			HandshakeTokenSource::Cookie => headers.push((String::from("Set-Cookie"), format!("{HANDSHAKE_PARAM}=; Path=/; Max-Age=0"))),
+			HandshakeTokenSource::QueryParam => {
+				// send the browser back to the requested url once the cookies are set, so the token doesn't linger in the url
+				if let Some(url) = request_url(request) {
+					headers.push((String::from("Location"), remove_query_param(&url, HANDSHAKE_PARAM)));
+					headers.push((String::from("Cache-Control"), String::from("no-store")));
+
+					return Some(RequestState::handshake(AuthReason::HandshakeResolved, headers, None));
+				}
+			}
+		}
+
+		let Some(session_token) = payload.session_token() else {
+			let error = ClerkError::Unauthorized(String::from("Error: The session was signed out during the handshake!"));
+			return Some(RequestState::signed_out(Some(AuthReason::SessionTokenMissing), error).with_headers(headers));
+		};
+
+		let state = match validate_jwt_with_claims(session_token, jwks.clone()).await {
+			Ok(mut jwt) => {
+				// the handshake refreshes the session cookie, which carries the token from here on
+				jwt.token_source = Some(TokenSource::Cookie(SESSION_COOKIE.into()));
+				RequestState::signed_in(jwt)
+			}
+			Err(error) => RequestState::signed_out(Some(AuthReason::SessionTokenInvalid), error),
+		};
+
+		Some(state.with_headers(headers))
+	}
+
+	async fn sync<T: ClerkRequest, C>(&self, request: &T, client_uat: i64) -> Option<RequestState<C>> {
+		let url = request_url(request)?;
+		let is_development = self.options.is_development();
+
+		// the development browser token was passed in the url, the handshake exchanges it for cookies
+		if is_development && get_query_param(&url, DEV_BROWSER_PARAM).is_some() {
+			return Some(self.handshake(request, AuthReason::DevBrowserSync).await);
+		}
+
+		match &self.options.domain_mode {
+			DomainMode::Satellite { sign_in_url, .. } => {
+				if client_uat > 0 || !is_document_request(request) || get_query_param(&url, SYNCED_PARAM).is_some() {
+					return None;
+				}
+
+				if !is_development {
+					return Some(self.handshake(request, AuthReason::SatelliteCookieNeedsSyncing).await);
+				}
+
+				// development instances sync through the primary's sign-in page, which sends the browser back
+				let Some(sign_in_url) = sign_in_url else {
+					return Some(RequestState::signed_out(
+						Some(AuthReason::SatelliteCookieNeedsSyncing),
+						ClerkError::InternalServerError(String::from("Error: A sign in url is required to sync development satellites!")),
+					));
+				};
+				let location = append_query_param(sign_in_url, REDIRECT_URL_PARAM, &url);
+
+				Some(self.redirect(request, AuthReason::SatelliteCookieNeedsSyncing, location))
+			}
+			DomainMode::Primary { satellite_domains } if is_development => {
+				let redirect_url = get_query_param(&url, REDIRECT_URL_PARAM)?;
+
+				// only send the browser back to known satellites, otherwise this would be an open redirect
+				let host = url::Url::parse(&redirect_url).ok()?.host_str()?.to_string();
+				if !satellite_domains
+					.iter()
+					.any(|domain| host == *domain || host.ends_with(&format!(".{domain}")))
+				{
+					return None;
+				}
+
+				let mut location = redirect_url;
+				if let Some(dev_browser) = self.dev_browser_token(request) {
+					location = append_query_param(&location, DEV_BROWSER_PARAM, &dev_browser);
+				}
+				let location = append_query_param(&location, SYNCED_PARAM, "true");
+
+				Some(self.redirect(request, AuthReason::PrimaryRespondsToSyncing, location))
+			}
+			DomainMode::Primary { .. } => None,
+		}
+	}
+
+	/// Checks that the browser can be redirected, returning the url of the request and the headers of the redirect, or the
+	/// reason the request is signed out instead.
+	fn prepare_redirect<T: ClerkRequest>(&self, request: &T, reason: AuthReason) -> Result<(String, Vec<(String, String)>), AuthReason> {
+		// only browsers navigating to a page can be redirected, api calls have to be retried by the client
+		let Some(url) = request_url(request).filter(|_| is_document_request(request)) else {
+			return Err(reason);
+		};
+
+		let redirect_count = request
+			.get_cookie(REDIRECT_LOOP_COOKIE)
+			.and_then(|count| count.parse::<u32>().ok())
+			.unwrap_or(0);
+		if redirect_count >= self.options.redirect_loop_limit {
+			return Err(AuthReason::RedirectLoop);
+		}
+
+		let headers = vec![
+			(
+				String::from("Set-Cookie"),
+				format!("{REDIRECT_LOOP_COOKIE}={}; Path=/; Max-Age=3; SameSite=Lax; HttpOnly", redirect_count + 1),
+			),
+			(String::from("Cache-Control"), String::from("no-store")),
+		];
+
+		Ok((url, headers))
+	}
+
+	fn redirect<T: ClerkRequest, C>(&self, request: &T, reason: AuthReason, location: String) -> RequestState<C> {
+		match self.prepare_redirect(request, reason) {
+			Ok((_, mut headers)) => {
+				headers.push((String::from("Location"), location));
+				RequestState::handshake(reason, headers, None)
+			}
+			Err(reason) => refresh_required(reason),
+		}
+	}
+
+	/// The development browser token, which development instances use in place of third party cookies.
+	fn dev_browser_token<T: ClerkRequest>(&self, request: &T) -> Option<String> {
+		let from_url = request_url(request).and_then(|url| get_query_param(&url, DEV_BROWSER_PARAM));
+		from_url.or_else(|| request.get_cookie(DEV_BROWSER_PARAM))
+	}
+
+	async fn handshake<T: ClerkRequest, C>(&self, request: &T, reason: AuthReason) -> RequestState<C> {
+		let (url, mut headers) = match self.prepare_redirect(request, reason) {
+			Ok(redirect) => redirect,
+			Err(reason) => return refresh_required(reason),
+		};
+
+		match &self.options.handshake_mode {
+			HandshakeMode::Redirect => {
+				let mut location = format!(
+					"{}/v1/client/handshake?redirect_url={}&__clerk_hs_reason={}",
+					self.options.frontend_api_url(),
+					urlencode(remove_query_param(&url, DEV_BROWSER_PARAM)),
+					reason.as_str()
+				);
+				if self.options.is_development() {
+					if let Some(dev_browser) = self.dev_browser_token(request) {
+						location = append_query_param(&location, DEV_BROWSER_PARAM, &dev_browser);
+					}
+				}
+				headers.push((String::from("Location"), location));
+
+				RequestState::handshake(reason, headers, None)
+			}
+			HandshakeMode::Interstitial(clerk) => match self.interstitial(clerk).await {
+				Ok(markup) => {
+					headers.push((String::from("Content-Type"), String::from("text/html")));
+
+					RequestState::handshake(reason, headers, Some(markup.to_string()))
+				}
+				Err(error) => RequestState::signed_out(Some(reason), error),
+			},
+		}
+	}
+
+	async fn interstitial(&self, clerk: &Clerk) -> Result<Arc<String>, ClerkError> {
+		if let Some(markup) = self.interstitial.load_full() {
+			return Ok(markup);
+		}
+
+		let markup = Miscellaneous::get_public_interstitial(clerk, Some(&self.options.publishable_key.frontend_api), None)
+			.await
+			.map_err(|_| ClerkError::InternalServerError(String::from("Error: Could not fetch the interstitial page!")))?;
+		let markup = Arc::new(markup);
+		self.interstitial.store(Some(markup.clone()));
+
+		Ok(markup)
+	}
+}
+
+/// The state of requests whose session has to be refreshed, but can't be redirected.
+fn refresh_required<C>(reason: AuthReason) -> RequestState<C> {
+	RequestState::signed_out(
+		Some(reason),
+		ClerkError::Unauthorized(format!("Error: The session has to be refreshed ({})", reason.as_str())),
+	)
+}
+
+/// Checks the `exp` claim of a token without verifying it.
+fn is_expired(token: &str) -> bool {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0);
+
+	matches!(get_unverified_claims(token).and_then(|claims| claims.exp), Some(exp) if exp <= now)
+}
+
+/// Whether the request is a browser navigating to a page, as opposed to a fetch or an api call.
+fn is_document_request<T: ClerkRequest>(request: &T) -> bool {
+	if request.get_method().is_some_and(|method| !method.eq_ignore_ascii_case("GET")) {
+		return false;
+	}
+
+	match request.get_header("Sec-Fetch-Dest") {
+		Some(dest) => dest == "document" || dest == "iframe",
+		None => request.get_header("Accept").is_some_and(|accept| accept.starts_with("text/html")),
+	}
+}
+
+/// Rebuilds the absolute url of the request, so the handshake can redirect back to it.
+fn request_url<T: ClerkRequest>(request: &T) -> Option<String> {
+	let uri = request.get_uri()?;
+	// proxies may append to the forwarded headers, the first value is the one the client sent
+	let forwarded = |key| {
+		request
+			.get_header(key)
+			.and_then(|val| val.split(',').next().map(|val| val.trim().to_string()))
+	};
+
+	let host = forwarded("X-Forwarded-Host").or_else(|| request.get_header("Host"))?;
+	let proto = forwarded("X-Forwarded-Proto").unwrap_or_else(|| {
+		if host.starts_with("localhost") || host.starts_with("127.0.0.1") {
+			String::from("http")
+		} else {
+			String::from("https")
+		}
+	});
+
+	Some(format!("{proto}://{host}{uri}"))
+}
+
+/// Returns the value of a query param of a url, or of the path and query of a request.
+pub(crate) fn get_query_param(url: &str, key: &str) -> Option<String> {
+	let (_, query) = url.split_once('?')?;
+	url::form_urlencoded::parse(query.as_bytes())
+		.find(|(name, _)| name == key)
+		.map(|(_, value)| value.into_owned())
+}
+
+fn append_query_param(url: &str, key: &str, value: &str) -> String {
+	let separator = if url.contains('?') { '&' } else { '?' };
+	format!("{url}{separator}{key}={}", urlencode(value))
+}
+
+fn remove_query_param(url: &str, key: &str) -> String {
+	let Some((base, query)) = url.split_once('?') else {
+		return url.to_string();
+	};
+
+	let query: String = url::form_urlencoded::Serializer::new(String::new())
+		.extend_pairs(url::form_urlencoded::parse(query.as_bytes()).filter(|(name, _)| name != key))
+		.finish();
+
+	match query.is_empty() {
+		true => base.to_string(),
+		false => format!("{base}?{query}"),
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::validators::{authorizer::tests::Helper, handshake::tests::sign_handshake, jwks::tests::StaticJwksProvider};
+	use std::collections::HashMap;
+
+	// base64("clerk.example.com$")
+	const PUBLISHABLE_KEY: &str = "pk_live_Y2xlcmsuZXhhbXBsZS5jb20k";
+	// base64("happy-hippo-1.clerk.accounts.dev$")
+	const DEV_PUBLISHABLE_KEY: &str = "pk_test_aGFwcHktaGlwcG8tMS5jbGVyay5hY2NvdW50cy5kZXYk";
+	const KID: &str = "bc63c2e9-5d1c-4e32-9b62-178f60409abd";
+
+	#[derive(Default)]
+	pub(crate) struct MockRequest {
+		headers: HashMap<String, String>,
+		cookies: HashMap<String, String>,
+		uri: Option<String>,
+	}
+
+	impl MockRequest {
+		fn document() -> Self {
+			Self::default().header("Host", "app.example.com").header("Sec-Fetch-Dest", "document")
+		}
+
+		pub fn header(mut self, key: &str, value: &str) -> Self {
+			self.headers.insert(key.to_lowercase(), value.to_string());
+			self
+		}
+
+		pub fn cookie(mut self, key: &str, value: &str) -> Self {
+			self.cookies.insert(key.to_string(), value.to_string());
+			self
+		}
+
+		pub fn uri(mut self, uri: &str) -> Self {
+			self.uri = Some(uri.to_string());
+			self
+		}
+	}
+
+	impl ClerkRequest for MockRequest {
+		fn get_header(&self, key: &str) -> Option<String> {
+			self.headers.get(&key.to_lowercase()).cloned()
+		}
+
+		fn get_cookie(&self, key: &str) -> Option<String> {
+			self.cookies.get(key).cloned()
+		}
+
+		fn get_uri(&self) -> Option<String> {
+			Some(self.uri.clone().unwrap_or_else(|| String::from("/dashboard?tab=1")))
+		}
+
+		fn get_method(&self) -> Option<String> {
+			Some(String::from("GET"))
+		}
+	}
+
+	fn setup() -> (Helper, Arc<StaticJwksProvider>, RequestAuthenticator) {
+		let helper = Helper::new();
+		let jwks = Arc::new(StaticJwksProvider::from_key(helper.jwks_key(KID)));
+		let authenticator = RequestAuthenticator::new(AuthenticateRequestOptions::new(PUBLISHABLE_KEY).unwrap());
+
+		(helper, jwks, authenticator)
+	}
+
+	fn now() -> usize {
+		SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as usize
+	}
+
+	#[tokio::test]
+	async fn test_signed_in_with_session_cookie() {
+		let (helper, jwks, authenticator) = setup();
+		let token = helper.generate_jwt_token(Some(KID), Some(now()), false);
+		let request = MockRequest::document()
+			.cookie("__session", &token)
+			.cookie("__client_uat", &(now() - 10).to_string());
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedIn);
+		let jwt = state.jwt.unwrap();
+		assert_eq!(jwt.sub, "user");
+		assert_eq!(jwt.token_source, Some(TokenSource::Cookie(String::from("__session"))));
+	}
+
+	#[tokio::test]
+	async fn test_signed_out_without_cookies() {
+		let (_, jwks, authenticator) = setup();
+
+		let state: RequestState = authenticator
+			.authenticate(&jwks, &TokenExtractor::defaults(true), &MockRequest::document())
+			.await;
+		assert_eq!(state.status, AuthStatus::SignedOut);
+		assert_eq!(state.reason, Some(AuthReason::SessionTokenAndUatMissing));
+		assert_eq!(state.status_code(), 401);
+	}
+
+	#[tokio::test]
+	async fn test_handshake_when_session_cookie_missing() {
+		let (_, jwks, authenticator) = setup();
+		let request = MockRequest::document().cookie("__client_uat", "1700000000");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::Handshake);
+		assert_eq!(state.reason, Some(AuthReason::SessionTokenMissing));
+		assert_eq!(state.status_code(), 307);
+
+		let location = state.headers.iter().find(|(key, _)| key == "Location").map(|(_, val)| val.as_str());
+		assert_eq!(
+			location,
+			Some("https://clerk.example.com/v1/client/handshake?redirect_url=https%3A%2F%2Fapp.example.com%2Fdashboard%3Ftab%3D1&__clerk_hs_reason=client-uat-but-no-session-token")
+		);
+	}
+
+	#[tokio::test]
+	async fn test_handshake_when_session_token_expired() {
+		let (helper, jwks, authenticator) = setup();
+		let token = helper.generate_jwt_token(Some(KID), None, true);
+		let request = MockRequest::document()
+			.header("X-Forwarded-Proto", "http")
+			.cookie("__session", &token)
+			.cookie("__client_uat", "1");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::Handshake);
+		assert_eq!(state.reason, Some(AuthReason::SessionTokenExpired));
+	}
+
+	#[tokio::test]
+	async fn test_handshake_when_token_issued_before_client_uat() {
+		let (helper, jwks, authenticator) = setup();
+		let token = helper.generate_jwt_token(Some(KID), Some(now() - 100), false);
+		let request = MockRequest::document()
+			.cookie("__session", &token)
+			.cookie("__client_uat", &now().to_string());
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.reason, Some(AuthReason::SessionTokenIatBeforeClientUat));
+	}
+
+	#[tokio::test]
+	async fn test_no_handshake_for_api_requests() {
+		let (_, jwks, authenticator) = setup();
+		let request = MockRequest::default()
+			.header("Host", "app.example.com")
+			.header("Sec-Fetch-Dest", "empty")
+			.cookie("__client_uat", "1700000000");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedOut);
+		assert_eq!(state.reason, Some(AuthReason::SessionTokenMissing));
+	}
+
+	#[tokio::test]
+	async fn test_redirect_loop() {
+		let (_, jwks, authenticator) = setup();
+		let request = MockRequest::document()
+			.cookie("__client_uat", "1700000000")
+			.cookie("__clerk_redirect_loop", "3");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedOut);
+		assert_eq!(state.reason, Some(AuthReason::RedirectLoop));
+	}
+
+	#[tokio::test]
+	async fn test_header_token_never_handshakes() {
+		let (helper, jwks, authenticator) = setup();
+		let token = helper.generate_jwt_token(Some(KID), None, true);
+		let request = MockRequest::document()
+			.header("Authorization", &format!("Bearer {token}"))
+			.cookie("__client_uat", "1700000000");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedOut);
+		assert_eq!(state.reason, Some(AuthReason::HeaderTokenInvalid));
+	}
+
+	#[tokio::test]
+	async fn test_resolve_handshake_cookie() {
+		let (helper, jwks, authenticator) = setup();
+		let session_token = helper.generate_jwt_token(Some(KID), None, false);
+		let handshake = sign_handshake(
+			&helper,
+			serde_json::json!([format!("__session={session_token}; Path=/"), "__client_uat=1700000000; Path=/"]),
+		);
+		// the stale cookies sent along are superseded by the handshake
+		let request = MockRequest::document()
+			.cookie("__clerk_handshake", &handshake)
+			.cookie("__session", "stale");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedIn);
+		assert_eq!(
+			state.headers,
+			vec![
+				(String::from("Set-Cookie"), format!("__session={session_token}; Path=/")),
+				(String::from("Set-Cookie"), String::from("__client_uat=1700000000; Path=/")),
+				(String::from("Set-Cookie"), String::from("__clerk_handshake=; Path=/; Max-Age=0")),
+			]
+		);
+		assert_eq!(state.jwt.unwrap().token_source, Some(TokenSource::Cookie(String::from("__session"))));
+	}
+
+	#[tokio::test]
+	async fn test_resolve_handshake_query_param() {
+		let (helper, jwks, authenticator) = setup();
+		let handshake = sign_handshake(&helper, serde_json::json!(["__client_uat=0; Path=/"]));
+		let request = MockRequest::document().uri(&format!("/dashboard?__clerk_handshake={handshake}&tab=1"));
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::Handshake);
+		assert_eq!(state.reason, Some(AuthReason::HandshakeResolved));
+
+		let location = state.headers.iter().find(|(key, _)| key == "Location").map(|(_, val)| val.as_str());
+		assert_eq!(location, Some("https://app.example.com/dashboard?tab=1"));
+	}
+
+	fn location(state: &RequestState) -> Option<&str> {
+		state.headers.iter().find(|(key, _)| key == "Location").map(|(_, val)| val.as_str())
+	}
+
+	fn authenticator(publishable_key: &str, domain_mode: DomainMode) -> RequestAuthenticator {
+		RequestAuthenticator::new(AuthenticateRequestOptions::new(publishable_key).unwrap().with_domain_mode(domain_mode))
+	}
+
+	#[tokio::test]
+	async fn test_satellite_needs_syncing() {
+		let jwks = Arc::new(StaticJwksProvider::from_key(Helper::new().jwks_key(KID)));
+		let authenticator = authenticator(
+			PUBLISHABLE_KEY,
+			DomainMode::Satellite {
+				domain: String::from("example-docs.com"),
+				sign_in_url: None,
+			},
+		);
+		let request = MockRequest::document().header("Host", "example-docs.com");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.reason, Some(AuthReason::SatelliteCookieNeedsSyncing));
+		assert!(location(&state)
+			.unwrap()
+			.starts_with("https://clerk.example-docs.com/v1/client/handshake?"));
+
+		// the browser is coming back from the primary
+		let request = MockRequest::document().header("Host", "example-docs.com").uri("/?__clerk_synced=true");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedOut);
+		assert_eq!(state.reason, Some(AuthReason::SessionTokenAndUatMissing));
+	}
+
+	#[tokio::test]
+	async fn test_development_satellite_syncs_through_sign_in() {
+		let jwks = Arc::new(StaticJwksProvider::from_key(Helper::new().jwks_key(KID)));
+		let authenticator = authenticator(
+			DEV_PUBLISHABLE_KEY,
+			DomainMode::Satellite {
+				domain: String::from("localhost:3001"),
+				sign_in_url: Some(String::from("http://localhost:3000/sign-in")),
+			},
+		);
+		let request = MockRequest::document().header("Host", "localhost:3001").uri("/docs");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::Handshake);
+		assert_eq!(
+			location(&state),
+			Some("http://localhost:3000/sign-in?__clerk_redirect_url=http%3A%2F%2Flocalhost%3A3001%2Fdocs")
+		);
+	}
+
+	#[tokio::test]
+	async fn test_development_primary_responds_to_syncing() {
+		let jwks = Arc::new(StaticJwksProvider::from_key(Helper::new().jwks_key(KID)));
+		let authenticator = authenticator(
+			DEV_PUBLISHABLE_KEY,
+			DomainMode::Primary {
+				satellite_domains: vec![String::from("example-docs.com")],
+			},
+		);
+
+		let request = MockRequest::document()
+			.uri("/sign-in?__clerk_redirect_url=https%3A%2F%2Fexample-docs.com%2Fdocs")
+			.cookie("__clerk_db_jwt", "dvb_123");
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.reason, Some(AuthReason::PrimaryRespondsToSyncing));
+		assert_eq!(
+			location(&state),
+			Some("https://example-docs.com/docs?__clerk_db_jwt=dvb_123&__clerk_synced=true")
+		);
+
+		// unknown domains are not redirected to
+		let request = MockRequest::document().uri("/sign-in?__clerk_redirect_url=https%3A%2F%2Fevil.com%2F");
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.status, AuthStatus::SignedOut);
+	}
+
+	#[tokio::test]
+	async fn test_development_handshake_carries_dev_browser() {
+		let jwks = Arc::new(StaticJwksProvider::from_key(Helper::new().jwks_key(KID)));
+		let authenticator = authenticator(DEV_PUBLISHABLE_KEY, DomainMode::default());
+		let request = MockRequest::document().uri("/?__clerk_db_jwt=dvb_123");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert_eq!(state.reason, Some(AuthReason::DevBrowserSync));
+		assert_eq!(
+			location(&state),
+			Some("https://happy-hippo-1.clerk.accounts.dev/v1/client/handshake?redirect_url=https%3A%2F%2Fapp.example.com%2F&__clerk_hs_reason=dev-browser-sync&__clerk_db_jwt=dvb_123")
+		);
+	}
+
+	#[tokio::test]
+	async fn test_proxy_url() {
+		let jwks = Arc::new(StaticJwksProvider::from_key(Helper::new().jwks_key(KID)));
+		let options = AuthenticateRequestOptions::new(PUBLISHABLE_KEY)
+			.unwrap()
+			.with_proxy_url("https://app.example.com/__clerk/");
+		let authenticator = RequestAuthenticator::new(options);
+		let request = MockRequest::document().cookie("__client_uat", "1700000000");
+
+		let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+		assert!(location(&state)
+			.unwrap()
+			.starts_with("https://app.example.com/__clerk/v1/client/handshake?"));
+	}
+
+	#[test]
+	fn test_remove_query_param() {
+		assert_eq!(
+			remove_query_param("https://app.example.com/dashboard?tab=1&__clerk_handshake=token", HANDSHAKE_PARAM),
+			"https://app.example.com/dashboard?tab=1"
+		);
+		assert_eq!(
+			remove_query_param("https://app.example.com/?__clerk_handshake=token", HANDSHAKE_PARAM),
+			"https://app.example.com/"
+		);
+	}
+
+	#[tokio::test]
+	async fn test_interstitial() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/public/interstitial")
+			.match_query(mockito::Matcher::Any)
+			.expect(1)
+			.with_body("<html>interstitial</html>")
+			.create_async()
+			.await;
+
+		let config = crate::ClerkConfiguration {
+			base_path: format!("{}/v1", server.url()),
+			..Default::default()
+		};
+		let options = AuthenticateRequestOptions::new(PUBLISHABLE_KEY)
+			.unwrap()
+			.with_handshake_mode(HandshakeMode::Interstitial(Clerk::new(config)));
+		let authenticator = RequestAuthenticator::new(options);
+		let jwks = Arc::new(StaticJwksProvider::from_key(Helper::new().jwks_key(KID)));
+		let request = MockRequest::document().cookie("__client_uat", "1700000000");
+
+		for _ in 0..2 {
+			let state: RequestState = authenticator.authenticate(&jwks, &TokenExtractor::defaults(true), &request).await;
+			assert_eq!(state.status, AuthStatus::Handshake);
+			assert_eq!(state.status_code(), 401);
+			assert_eq!(state.response_body(), "<html>interstitial</html>");
+		}
+
+		mock.assert_async().await;
+	}
+}
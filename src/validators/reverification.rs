@@ -0,0 +1,160 @@
+use serde_json::json;
+use std::time::Duration;
+
+/// Reason of the 403 responses the Clerk frontend SDKs answer with a reverification prompt.
+pub const REVERIFICATION_REASON: &str = "reverification-error";
+
+/// The `fva` claim of a session token: how many minutes ago the user last verified their first and second factor.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "[i64; 2]", into = "[i64; 2]")]
+pub struct FactorVerificationAge {
+	/// `None` if the first factor was never verified in this session.
+	pub first_factor: Option<i64>,
+	/// `None` if the user has no second factor, or never verified it in this session.
+	pub second_factor: Option<i64>,
+}
+
+impl From<[i64; 2]> for FactorVerificationAge {
+	fn from([first_factor, second_factor]: [i64; 2]) -> Self {
+		// the claim uses -1 for factors that weren't verified
+		Self {
+			first_factor: Some(first_factor).filter(|age| *age >= 0),
+			second_factor: Some(second_factor).filter(|age| *age >= 0),
+		}
+	}
+}
+
+impl From<FactorVerificationAge> for [i64; 2] {
+	fn from(age: FactorVerificationAge) -> Self {
+		[age.first_factor.unwrap_or(-1), age.second_factor.unwrap_or(-1)]
+	}
+}
+
+/// Which factors a reverification requires.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReverificationLevel {
+	FirstFactor,
+	/// The second factor, or the first one for users without a second factor.
+	SecondFactor,
+	/// Both factors, or only the first one for users without a second factor.
+	MultiFactor,
+}
+
+/// A step-up requirement: the user has to have verified the factors of the level within the max age.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reverification {
+	pub level: ReverificationLevel,
+	pub max_age: Duration,
+}
+
+impl Reverification {
+	pub fn new(level: ReverificationLevel, max_age: Duration) -> Self {
+		Self { level, max_age }
+	}
+
+	/// Both factors within the last 10 minutes.
+	pub fn strict_mfa() -> Self {
+		Self::new(ReverificationLevel::MultiFactor, Duration::from_secs(10 * 60))
+	}
+
+	/// The second factor within the last 10 minutes.
+	pub fn strict() -> Self {
+		Self::new(ReverificationLevel::SecondFactor, Duration::from_secs(10 * 60))
+	}
+
+	/// The second factor within the last hour.
+	pub fn moderate() -> Self {
+		Self::new(ReverificationLevel::SecondFactor, Duration::from_secs(60 * 60))
+	}
+
+	/// The second factor within the last day.
+	pub fn lax() -> Self {
+		Self::new(ReverificationLevel::SecondFactor, Duration::from_secs(24 * 60 * 60))
+	}
+
+	fn max_age_minutes(&self) -> i64 {
+		(self.max_age.as_secs() / 60) as i64
+	}
+
+	/// Checks the `fva` claim of a session against the requirement. Tokens without the claim never satisfy it.
+	pub fn is_satisfied_by(&self, age: Option<&FactorVerificationAge>) -> bool {
+		let Some(age) = age else {
+			return false;
+		};
+
+		let max_age = self.max_age_minutes();
+		let first_factor = age.first_factor.is_some_and(|age| age < max_age);
+		let second_factor = age.second_factor.map(|age| age < max_age);
+
+		match (self.level, second_factor) {
+			(ReverificationLevel::FirstFactor, _) => first_factor,
+			(ReverificationLevel::SecondFactor, Some(second_factor)) => second_factor,
+			(ReverificationLevel::MultiFactor, Some(second_factor)) => first_factor && second_factor,
+			// users without a second factor can only verify the first one
+			(_, None) => first_factor,
+		}
+	}
+
+	/// The body of the 403 response the Clerk frontend SDKs answer with a reverification prompt.
+	pub fn error_body(&self) -> String {
+		json!({
+			"clerk_error": {
+				"type": "forbidden",
+				"reason": REVERIFICATION_REASON,
+				"metadata": {
+					"reverification": {
+						"level": self.level,
+						"afterMinutes": self.max_age_minutes(),
+					},
+				},
+			},
+		})
+		.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn age(first_factor: i64, second_factor: i64) -> FactorVerificationAge {
+		FactorVerificationAge::from([first_factor, second_factor])
+	}
+
+	#[test]
+	fn test_reverification_levels() {
+		let first_factor = Reverification::new(ReverificationLevel::FirstFactor, Duration::from_secs(10 * 60));
+		assert!(first_factor.is_satisfied_by(Some(&age(5, -1))));
+		assert!(!first_factor.is_satisfied_by(Some(&age(10, 0))));
+
+		let strict = Reverification::strict();
+		assert!(strict.is_satisfied_by(Some(&age(60, 5))));
+		assert!(!strict.is_satisfied_by(Some(&age(5, 60))));
+		// users without a second factor fall back to the first one
+		assert!(strict.is_satisfied_by(Some(&age(5, -1))));
+
+		let strict_mfa = Reverification::strict_mfa();
+		assert!(strict_mfa.is_satisfied_by(Some(&age(5, 5))));
+		assert!(!strict_mfa.is_satisfied_by(Some(&age(60, 5))));
+
+		assert!(!Reverification::lax().is_satisfied_by(None));
+		assert!(!Reverification::lax().is_satisfied_by(Some(&age(-1, -1))));
+	}
+
+	#[test]
+	fn test_reverification_error_body() {
+		let body: serde_json::Value = serde_json::from_str(&Reverification::strict().error_body()).unwrap();
+
+		assert_eq!(
+			body,
+			json!({
+				"clerk_error": {
+					"type": "forbidden",
+					"reason": "reverification-error",
+					"metadata": { "reverification": { "level": "second_factor", "afterMinutes": 10 } }
+				}
+			})
+		);
+	}
+}
@@ -1,12 +1,18 @@
 use crate::validators::{
-	authorizer::{ClerkAuthorizer, ClerkError, ClerkRequest},
+	authorizer::{AuthCheck, ClerkAuthorizer, ClerkError, ClerkRequest},
 	jwks::JwksProvider,
+	request_state::{AuthStatus, RequestState},
+	session_status::SessionStatusScope,
 };
 use rocket::{
-	http::Status,
+	fairing::{Fairing, Info, Kind},
+	http::{Header, Status},
 	request::{FromRequest, Outcome},
-	Request,
+	response::{self, Responder},
+	Request, Response,
 };
+use serde::de::DeserializeOwned;
+use std::io::Cursor;
 
 use super::authorizer::ClerkJwt;
 
@@ -19,35 +25,66 @@ impl<'r> ClerkRequest for &'r Request<'_> {
 	fn get_cookie(&self, key: &str) -> Option<String> {
 		self.cookies().get(key).map(|cookie| cookie.value().to_string())
 	}
+
+	fn get_uri(&self) -> Option<String> {
+		Some(self.uri().to_string())
+	}
+
+	fn get_method(&self) -> Option<String> {
+		Some(self.method().to_string())
+	}
 }
 
-pub struct ClerkGuardConfig<J: JwksProvider> {
-	pub authorizer: ClerkAuthorizer<J>,
+pub struct ClerkGuardConfig<J: JwksProvider, C = ()> {
+	pub authorizer: ClerkAuthorizer<J, C>,
 	pub routes: Option<Vec<String>>,
+	pub session_status: SessionStatusScope,
 }
 
 impl<J: JwksProvider> ClerkGuardConfig<J> {
 	pub fn new(jwks_provider: J, routes: Option<Vec<String>>, validate_session_cookie: bool) -> Self {
 		let authorizer = ClerkAuthorizer::new(jwks_provider, validate_session_cookie);
-		Self { authorizer, routes }
+		Self::from_authorizer(authorizer, routes)
 	}
 }
 
-pub struct ClerkGuard<J: JwksProvider + Send + Sync> {
-	pub jwt: Option<ClerkJwt>,
+impl<J: JwksProvider, C> ClerkGuardConfig<J, C> {
+	/// Creates the config from a configured authorizer, e.g. one using [`ClerkAuthorizer::with_request_state`].
+	///
+	/// Register [`clerk_handshake_catcher`] as well so that browsers are sent through the handshake.
+	pub fn from_authorizer(authorizer: ClerkAuthorizer<J, C>, routes: Option<Vec<String>>) -> Self {
+		Self {
+			authorizer,
+			routes,
+			session_status: SessionStatusScope::default(),
+		}
+	}
+
+	/// Selects the routes that also confirm the session is still active, see [`ClerkAuthorizer::with_session_status`].
+	pub fn with_session_status(mut self, scope: SessionStatusScope) -> Self {
+		self.session_status = scope;
+		self
+	}
+}
+
+/// Request guard that authorizes the request with the [`ClerkGuardConfig<J, C>`] in managed state.
+///
+/// `C` are the custom claims of the authorizer, see [`ClerkAuthorizer::with_claims`].
+pub struct ClerkGuard<J: JwksProvider + Send + Sync, C = ()> {
+	pub jwt: Option<ClerkJwt<C>>,
 	_marker: std::marker::PhantomData<J>,
 }
 
 // Implement request guard for ClerkGuard
 #[rocket::async_trait]
-impl<'r, J: JwksProvider + Send + Sync + 'static> FromRequest<'r> for ClerkGuard<J> {
+impl<'r, J: JwksProvider + Send + Sync + 'static, C: DeserializeOwned + Clone + Send + Sync + 'static> FromRequest<'r> for ClerkGuard<J, C> {
 	type Error = ClerkError;
 
 	async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
 		// Retrieve the ClerkAuthorizer from managed state
 		let config = request
 			.rocket()
-			.state::<ClerkGuardConfig<J>>()
+			.state::<ClerkGuardConfig<J, C>>()
 			.expect("ClerkGuardConfig not found in managed state");
 
 		match &config.routes {
@@ -69,7 +106,23 @@ impl<'r, J: JwksProvider + Send + Sync + 'static> FromRequest<'r> for ClerkGuard
 			None => {}
 		}
 
-		match config.authorizer.authorize(&request).await {
+		let verify_session_status = config.session_status.applies_to(request.uri().path().as_str());
+		let state = config.authorizer.authenticate_request_with_status(&request, verify_session_status).await;
+
+		// Keep the state around for the catcher and the fairing, which respond with the handshake or the refreshed cookies
+		if state.status == AuthStatus::Handshake || !state.headers.is_empty() {
+			request.local_cache(|| Some(state.without_jwt()));
+		}
+
+		// Rocket only lets guards fail with an error status, the catcher turns it into the handshake response
+		if state.status == AuthStatus::Handshake {
+			return Outcome::Error((
+				Status::Unauthorized,
+				ClerkError::Unauthorized(String::from("Error: The session has to be refreshed!")),
+			));
+		}
+
+		match state.into_result() {
 			Ok(jwt) => {
 				request.local_cache(|| jwt.clone());
 				return Outcome::Success(ClerkGuard {
@@ -77,10 +130,171 @@ impl<'r, J: JwksProvider + Send + Sync + 'static> FromRequest<'r> for ClerkGuard
 					_marker: std::marker::PhantomData,
 				});
 			}
-			Err(error) => match error {
-				ClerkError::Unauthorized(msg) => Outcome::Error((Status::Unauthorized, ClerkError::Unauthorized(msg))),
-				ClerkError::InternalServerError(msg) => Outcome::Error((Status::InternalServerError, ClerkError::InternalServerError(msg))),
-			},
+			Err(error) => Outcome::Error((Status::new(error.status_code()), error)),
+		}
+	}
+}
+
+/// The check a [`ClerkAuthCheckGuard`] enforces.
+///
+/// # Example
+/// ```
+/// struct IsAdmin;
+///
+/// impl AuthCheckPolicy for IsAdmin {
+///     fn check() -> AuthCheck {
+///         AuthCheck::role("org:admin")
+///     }
+/// }
+///
+/// #[get("/admin")]
+/// fn admin(guard: ClerkAuthCheckGuard<MemoryCacheJwksProvider, IsAdmin>) -> &'static str {
+///     "Hello admin!"
+/// }
+/// ```
+pub trait AuthCheckPolicy {
+	fn check() -> AuthCheck;
+}
+
+/// Request guard that authorizes the request like [`ClerkGuard`], then fails with a 403 unless the session passes the
+/// check of the policy `P`.
+pub struct ClerkAuthCheckGuard<J: JwksProvider + Send + Sync, P: AuthCheckPolicy, C = ()> {
+	pub jwt: ClerkJwt<C>,
+	_marker: std::marker::PhantomData<fn() -> (J, P)>,
+}
+
+#[rocket::async_trait]
+impl<'r, J, P, C> FromRequest<'r> for ClerkAuthCheckGuard<J, P, C>
+where
+	J: JwksProvider + Send + Sync + 'static,
+	P: AuthCheckPolicy,
+	C: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+	type Error = ClerkError;
+
+	async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+		let jwt = match request.guard::<ClerkGuard<J, C>>().await {
+			Outcome::Success(ClerkGuard { jwt: Some(jwt), .. }) => jwt,
+			// the route is not protected by the ClerkGuardConfig
+			Outcome::Success(ClerkGuard { jwt: None, .. }) => {
+				return Outcome::Error((
+					Status::Unauthorized,
+					ClerkError::Unauthorized(String::from("Error: The request is not signed in!")),
+				))
+			}
+			Outcome::Error(error) => return Outcome::Error(error),
+			Outcome::Forward(status) => return Outcome::Forward(status),
+		};
+
+		match jwt.require(&P::check()) {
+			Ok(()) => Outcome::Success(ClerkAuthCheckGuard {
+				jwt,
+				_marker: std::marker::PhantomData,
+			}),
+			Err(error) => {
+				// Keep the error around for the catcher, which responds with the reverification JSON
+				request.local_cache(|| Some(error.clone()));
+				Outcome::Error((Status::new(error.status_code()), error))
+			}
+		}
+	}
+}
+
+/// Catcher that responds with the handshake redirect or interstitial page of requests rejected by [`ClerkGuard`].
+///
+/// Attach [`ClerkHandshakeFairing`] as well so the cookies refreshed by a handshake are set on accepted requests.
+///
+/// # Example
+/// ```
+/// rocket::build()
+///     .mount("/", routes![index])
+///     .register("/", catchers![clerk_handshake_catcher])
+///     .attach(ClerkHandshakeFairing)
+///     .manage(clerk_config)
+/// ```
+#[rocket::catch(401)]
+pub fn clerk_handshake_catcher(request: &Request) -> ClerkHandshakeResponse {
+	let state = request.local_cache(|| None::<RequestState>).clone();
+	ClerkHandshakeResponse(state.filter(|state| !state.is_signed_in()))
+}
+
+/// Response of [`clerk_handshake_catcher`], a plain 401 when the request didn't need a handshake.
+pub struct ClerkHandshakeResponse(pub Option<RequestState>);
+
+impl<'r> Responder<'r, 'static> for ClerkHandshakeResponse {
+	fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+		let Some(state) = self.0 else {
+			return Response::build().status(Status::Unauthorized).ok();
+		};
+
+		let body = state.response_body();
+		let mut response = Response::build();
+		response
+			.status(Status::new(state.status_code()))
+			.sized_body(body.len(), Cursor::new(body));
+		for (key, val) in state.headers {
+			response.header_adjoin(Header::new(key, val));
+		}
+
+		response.ok()
+	}
+}
+
+/// Catcher that responds to requests rejected by [`ClerkAuthCheckGuard`] with the error message, including the
+/// "reverification required" JSON the Clerk frontend SDKs prompt the user with.
+///
+/// # Example
+/// ```
+/// rocket::build()
+///     .mount("/", routes![index])
+///     .register("/", catchers![clerk_handshake_catcher, clerk_forbidden_catcher])
+///     .manage(clerk_config)
+/// ```
+#[rocket::catch(403)]
+pub fn clerk_forbidden_catcher(request: &Request) -> ClerkErrorResponse {
+	ClerkErrorResponse(request.local_cache(|| None::<ClerkError>).clone())
+}
+
+/// Response of [`clerk_forbidden_catcher`], a plain 403 when the request wasn't rejected by a Clerk guard.
+pub struct ClerkErrorResponse(pub Option<ClerkError>);
+
+impl<'r> Responder<'r, 'static> for ClerkErrorResponse {
+	fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+		let Some(error) = self.0 else {
+			return Response::build().status(Status::Forbidden).ok();
+		};
+
+		let body = error.message();
+		let mut response = Response::build();
+		response
+			.status(Status::new(error.status_code()))
+			.sized_body(body.len(), Cursor::new(body));
+		if let Some(content_type) = error.content_type() {
+			response.header(Header::new("Content-Type", content_type));
+		}
+
+		response.ok()
+	}
+}
+
+/// Fairing that sets the cookies refreshed by a handshake on the responses of requests accepted by [`ClerkGuard`].
+pub struct ClerkHandshakeFairing;
+
+#[rocket::async_trait]
+impl Fairing for ClerkHandshakeFairing {
+	fn info(&self) -> Info {
+		Info {
+			name: "Clerk handshake cookies",
+			kind: Kind::Response,
+		}
+	}
+
+	async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+		// rejected requests are answered by the catcher, which already carries the headers
+		if let Some(state) = request.local_cache(|| None::<RequestState>).as_ref().filter(|state| state.is_signed_in()) {
+			for (key, val) in &state.headers {
+				response.adjoin_header(Header::new(key.clone(), val.clone()));
+			}
 		}
 	}
 }
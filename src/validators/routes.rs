@@ -0,0 +1,82 @@
+/// Whether a request path matches a route pattern.
+///
+/// Both are compared segment by segment after normalization: the query is dropped, empty segments (duplicate and trailing
+/// slashes) are ignored and percent escapes are decoded, so `/account/%64elete/` matches `/account/delete`. An encoded
+/// slash (`%2F`) stays part of its segment, the way routers see it.
+///
+/// A `{name}` segment in the pattern matches any single segment, e.g. `/users/{id}/delete`, and a trailing `*` segment
+/// matches the rest of the path, e.g. `/admin/*` matches `/admin` and everything below it.
+pub fn route_matches(pattern: &str, path: &str) -> bool {
+	let pattern = segments(pattern);
+	let path = segments(path);
+
+	match pattern.split_last() {
+		Some((last, prefix)) if last == "*" => path.len() >= prefix.len() && prefix.iter().zip(&path).all(segment_matches),
+		_ => pattern.len() == path.len() && pattern.iter().zip(&path).all(segment_matches),
+	}
+}
+
+fn segment_matches((pattern, segment): (&String, &String)) -> bool {
+	(pattern.starts_with('{') && pattern.ends_with('}')) || pattern == segment
+}
+
+fn segments(path: &str) -> Vec<String> {
+	let path = path.split(['?', '#']).next().unwrap_or_default();
+	path.split('/').filter(|segment| !segment.is_empty()).map(decode_segment).collect()
+}
+
+fn decode_segment(segment: &str) -> String {
+	let bytes = segment.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		let escaped = bytes[i] == b'%' && bytes.get(i + 1..i + 3).is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+		match escaped {
+			true => {
+				// both digits were checked above
+				let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap();
+				decoded.push(u8::from_str_radix(hex, 16).unwrap());
+				i += 3;
+			}
+			false => {
+				decoded.push(bytes[i]);
+				i += 1;
+			}
+		}
+	}
+	String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_route_matches_normalized_path() {
+		assert!(route_matches("/account/delete", "/account/delete"));
+		assert!(route_matches("/account/delete", "/account/delete/"));
+		assert!(route_matches("/account/delete", "//account//delete"));
+		assert!(route_matches("/account/delete", "/account/%64elete"));
+		assert!(route_matches("/account/delete", "/account/delete?confirm=1"));
+		assert!(!route_matches("/account/delete", "/account"));
+		assert!(!route_matches("/account/delete", "/account%2Fdelete"));
+		assert!(!route_matches("/account/delete", "/account/delete/now"));
+
+		assert!(route_matches("/", "/"));
+		assert!(route_matches("/", ""));
+		assert!(!route_matches("/", "/account"));
+	}
+
+	#[test]
+	fn test_route_matches_patterns() {
+		assert!(route_matches("/users/{id}/delete", "/users/user_123/delete"));
+		assert!(route_matches("/users/{id}/delete", "/users/user_123/delete/"));
+		assert!(!route_matches("/users/{id}/delete", "/users/delete"));
+
+		assert!(route_matches("/admin/*", "/admin"));
+		assert!(route_matches("/admin/*", "/admin/users/user_123"));
+		assert!(route_matches("/admin/*", "/%61dmin/users"));
+		assert!(!route_matches("/admin/*", "/administrators"));
+		assert!(route_matches("/*", "/anything"));
+	}
+}
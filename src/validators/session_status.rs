@@ -0,0 +1,332 @@
+use crate::{
+	apis::{sessions_api::Session, Error},
+	clerk::Clerk,
+	models::session::Status,
+	validators::{
+		authorizer::{ClerkError, ClerkJwt},
+		routes::route_matches,
+	},
+};
+use std::{
+	collections::HashMap,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+// expired entries are swept at most this often, rather than on every insert
+const CACHE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What to do when the Backend API can't be reached to check a session.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SessionStatusFailurePolicy {
+	/// Accept the request based on the verified token alone.
+	FailOpen,
+	/// Reject the request with an internal server error.
+	#[default]
+	FailClosed,
+}
+
+#[derive(Clone, Debug)]
+pub struct SessionStatusOptions {
+	/// How long the status of a session is cached for. Revoked sessions can still be used during this window, so it
+	/// should be well below the 60 seconds a session token is valid for.
+	pub cache_ttl: Duration,
+	pub failure_policy: SessionStatusFailurePolicy,
+}
+
+impl Default for SessionStatusOptions {
+	fn default() -> Self {
+		Self {
+			cache_ttl: Duration::from_secs(10),
+			failure_policy: SessionStatusFailurePolicy::default(),
+		}
+	}
+}
+
+struct CachedStatus {
+	active: bool,
+	expires_at: Instant,
+}
+
+struct StatusCache {
+	statuses: HashMap<String, CachedStatus>,
+	next_sweep: Instant,
+}
+
+/// Confirms with the Backend API that the session of a verified token is still `active`.
+///
+/// Session tokens stay valid for up to 60 seconds after their session is revoked. Routes that can't accept that window
+/// can check the session status on top of the token signature, see [`ClerkAuthorizer::with_session_status`](crate::validators::authorizer::ClerkAuthorizer::with_session_status).
+pub struct SessionStatusVerifier {
+	clerk: Clerk,
+	options: SessionStatusOptions,
+	cache: Mutex<StatusCache>,
+}
+
+impl SessionStatusVerifier {
+	pub fn new(clerk: Clerk) -> Self {
+		Self::new_with_options(clerk, SessionStatusOptions::default())
+	}
+
+	pub fn new_with_options(clerk: Clerk, options: SessionStatusOptions) -> Self {
+		Self {
+			clerk,
+			options,
+			cache: Mutex::new(StatusCache {
+				statuses: HashMap::new(),
+				next_sweep: Instant::now() + CACHE_SWEEP_INTERVAL,
+			}),
+		}
+	}
+
+	/// Checks that the session the jwt was issued for is still active.
+	pub async fn verify<C>(&self, jwt: &ClerkJwt<C>) -> Result<(), ClerkError> {
+		let Some(session_id) = &jwt.sid else {
+			return Err(ClerkError::Unauthorized(String::from("Error: The JWT is not bound to a session!")));
+		};
+
+		let active = match self.cached(session_id) {
+			Some(active) => active,
+			None => match Session::get_session(&self.clerk, session_id).await {
+				Ok(session) => self.store(session_id, session.status == Status::Active),
+				// the session was removed
+				Err(Error::ResponseError(content)) if content.status == reqwest::StatusCode::NOT_FOUND => self.store(session_id, false),
+				Err(error) => {
+					return match self.options.failure_policy {
+						SessionStatusFailurePolicy::FailOpen => {
+							log::warn!("clerk-rs: could not verify the session status, accepting the request: {}", error);
+							Ok(())
+						}
+						SessionStatusFailurePolicy::FailClosed => Err(ClerkError::InternalServerError(String::from(
+							"Error: Could not verify the session status!",
+						))),
+					};
+				}
+			},
+		};
+
+		match active {
+			true => Ok(()),
+			false => Err(ClerkError::Unauthorized(String::from("Error: The session is no longer active!"))),
+		}
+	}
+
+	fn cached(&self, session_id: &str) -> Option<bool> {
+		let cache = self.cache.lock().unwrap();
+		cache
+			.statuses
+			.get(session_id)
+			.filter(|status| status.expires_at > Instant::now())
+			.map(|status| status.active)
+	}
+
+	fn store(&self, session_id: &str, active: bool) -> bool {
+		let now = Instant::now();
+		let mut cache = self.cache.lock().unwrap();
+		if now >= cache.next_sweep {
+			cache.statuses.retain(|_, status| status.expires_at > now);
+			cache.next_sweep = now + CACHE_SWEEP_INTERVAL;
+		}
+
+		cache.statuses.insert(
+			session_id.to_string(),
+			CachedStatus {
+				active,
+				expires_at: now + self.options.cache_ttl,
+			},
+		);
+		active
+	}
+}
+
+/// The routes a middleware checks the session status on, in addition to verifying the token.
+///
+/// Routes are patterns matched against the normalized request path, see [`route_matches`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SessionStatusScope {
+	#[default]
+	Disabled,
+	AllRoutes,
+	Routes(Vec<String>),
+}
+
+impl SessionStatusScope {
+	pub fn applies_to(&self, path: &str) -> bool {
+		match self {
+			SessionStatusScope::Disabled => false,
+			SessionStatusScope::AllRoutes => true,
+			SessionStatusScope::Routes(routes) => routes.iter().any(|route| route_matches(route, path)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{validators::authorizer::Entitlements, ClerkConfiguration};
+	use serde_json::Map;
+
+	const SESSION_ID: &str = "sess_123";
+
+	fn session_body(status: &str) -> String {
+		format!(
+			r#"{{
+				"object": "session",
+				"id": "{SESSION_ID}",
+				"user_id": "user_123",
+				"client_id": "client_123",
+				"status": "{status}",
+				"last_active_at": 1700000000,
+				"expire_at": 1700000000,
+				"abandon_at": 1700000000,
+				"updated_at": 1700000000,
+				"created_at": 1700000000
+			}}"#
+		)
+	}
+
+	fn jwt(sid: Option<&str>) -> ClerkJwt {
+		ClerkJwt {
+			azp: None,
+			exp: 0,
+			iat: 0,
+			iss: String::from("issuer"),
+			nbf: 0,
+			sid: sid.map(String::from),
+			sub: String::from("user_123"),
+			act: None,
+			org: None,
+			features: Entitlements::default(),
+			plans: Entitlements::default(),
+			factor_verification_age: None,
+			claims: (),
+			other: Map::new(),
+			instance: None,
+			token_source: None,
+		}
+	}
+
+	fn verifier(url: String, options: SessionStatusOptions) -> SessionStatusVerifier {
+		let config = ClerkConfiguration {
+			base_path: format!("{}/v1", url),
+			..Default::default()
+		};
+
+		SessionStatusVerifier::new_with_options(Clerk::new(config), options)
+	}
+
+	#[tokio::test]
+	async fn test_session_status_active_is_cached() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/sessions/sess_123")
+			.expect(1)
+			.with_body(session_body("active"))
+			.create_async()
+			.await;
+
+		let verifier = verifier(server.url(), SessionStatusOptions::default());
+
+		verifier.verify(&jwt(Some(SESSION_ID))).await.expect("session should be active");
+		verifier.verify(&jwt(Some(SESSION_ID))).await.expect("session should be active");
+
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_session_status_revoked() {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("GET", "/v1/sessions/sess_123")
+			.with_body(session_body("revoked"))
+			.create_async()
+			.await;
+
+		let verifier = verifier(server.url(), SessionStatusOptions::default());
+
+		let res = verifier.verify(&jwt(Some(SESSION_ID))).await;
+		assert!(matches!(res, Err(ClerkError::Unauthorized(_))));
+	}
+
+	#[tokio::test]
+	async fn test_session_status_not_found() {
+		let mut server = mockito::Server::new_async().await;
+		server.mock("GET", "/v1/sessions/sess_123").with_status(404).create_async().await;
+
+		let verifier = verifier(server.url(), SessionStatusOptions::default());
+
+		let res = verifier.verify(&jwt(Some(SESSION_ID))).await;
+		assert!(matches!(res, Err(ClerkError::Unauthorized(_))));
+	}
+
+	#[tokio::test]
+	async fn test_session_status_failure_policy() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/v1/sessions/sess_123")
+			.expect(2)
+			.with_status(503)
+			.create_async()
+			.await;
+
+		let fail_closed = verifier(server.url(), SessionStatusOptions::default());
+		let res = fail_closed.verify(&jwt(Some(SESSION_ID))).await;
+		assert!(matches!(res, Err(ClerkError::InternalServerError(_))));
+
+		let fail_open = verifier(
+			server.url(),
+			SessionStatusOptions {
+				failure_policy: SessionStatusFailurePolicy::FailOpen,
+				..Default::default()
+			},
+		);
+		fail_open.verify(&jwt(Some(SESSION_ID))).await.expect("should fail open");
+
+		// failures are not cached
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_session_status_requires_sid() {
+		let verifier = verifier(String::from("http://localhost:1"), SessionStatusOptions::default());
+
+		let res = verifier.verify(&jwt(None)).await;
+		assert!(matches!(res, Err(ClerkError::Unauthorized(_))));
+	}
+
+	#[test]
+	fn test_session_status_cache_sweep_on_interval() {
+		let options = SessionStatusOptions {
+			cache_ttl: Duration::ZERO,
+			..Default::default()
+		};
+		let verifier = verifier(String::from("http://localhost:1"), options);
+
+		verifier.store("sess_1", true);
+		verifier.store("sess_2", true);
+		// expired statuses are kept until the next sweep
+		assert_eq!(verifier.cache.lock().unwrap().statuses.len(), 2);
+		assert_eq!(verifier.cached("sess_1"), None);
+
+		verifier.cache.lock().unwrap().next_sweep = Instant::now();
+		verifier.store("sess_3", true);
+		let cache = verifier.cache.lock().unwrap();
+		assert_eq!(cache.statuses.len(), 1);
+		assert!(cache.next_sweep > Instant::now());
+	}
+
+	#[test]
+	fn test_session_status_scope() {
+		assert!(!SessionStatusScope::Disabled.applies_to("/"));
+		assert!(SessionStatusScope::AllRoutes.applies_to("/"));
+
+		let scope = SessionStatusScope::Routes(vec![String::from("/billing"), String::from("/teams/{id}/billing")]);
+		assert!(scope.applies_to("/billing"));
+		assert!(scope.applies_to("/billing/"));
+		assert!(scope.applies_to("//billing"));
+		assert!(scope.applies_to("/%62illing"));
+		assert!(scope.applies_to("/teams/team_123/billing"));
+		assert!(!scope.applies_to("/"));
+		assert!(!scope.applies_to("/teams/team_123"));
+	}
+}
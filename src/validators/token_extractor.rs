@@ -0,0 +1,183 @@
+use crate::validators::{authorizer::ClerkRequest, request_state::get_query_param};
+
+/// Where a token is read from on a request.
+///
+/// [`ClerkAuthorizer::with_token_extractors`](crate::validators::authorizer::ClerkAuthorizer::with_token_extractors) takes
+/// an ordered list of extractors, the first one that finds a token wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenExtractor {
+	/// A request header. When a scheme is set (e.g. `Bearer`), it is matched case-insensitively and stripped from the
+	/// value, and values using another scheme are ignored.
+	Header { name: String, scheme: Option<String> },
+	/// A cookie, e.g. `__session`.
+	Cookie(String),
+	/// A query param, for clients that can't set headers, like WebSocket upgrades or `EventSource`.
+	QueryParam(String),
+}
+
+impl TokenExtractor {
+	/// The `Authorization: Bearer <token>` header.
+	pub fn bearer() -> Self {
+		Self::header("Authorization", Some("Bearer"))
+	}
+
+	/// The `__session` cookie set by the Clerk frontend SDKs.
+	pub fn session_cookie() -> Self {
+		Self::cookie("__session")
+	}
+
+	pub fn header(name: &str, scheme: Option<&str>) -> Self {
+		Self::Header {
+			name: name.to_string(),
+			scheme: scheme.map(String::from),
+		}
+	}
+
+	pub fn cookie(name: &str) -> Self {
+		Self::Cookie(name.to_string())
+	}
+
+	pub fn query_param(name: &str) -> Self {
+		Self::QueryParam(name.to_string())
+	}
+
+	/// The extractors used when none are configured: the bearer token, then the session cookie if it is validated.
+	pub fn defaults(validate_session_cookie: bool) -> Vec<Self> {
+		match validate_session_cookie {
+			true => vec![Self::bearer(), Self::session_cookie()],
+			false => vec![Self::bearer()],
+		}
+	}
+
+	/// Reads the token from the request, if present.
+	pub fn extract<T: ClerkRequest>(&self, request: &T) -> Option<ExtractedToken> {
+		let (token, source) = match self {
+			Self::Header { name, scheme } => {
+				let value = request.get_header(name)?;
+				let token = strip_scheme(value.trim(), scheme.as_deref())?;
+				(token.to_string(), TokenSource::Header(name.clone()))
+			}
+			Self::Cookie(name) => (request.get_cookie(name)?, TokenSource::Cookie(name.clone())),
+			Self::QueryParam(name) => (get_query_param(&request.get_uri()?, name)?, TokenSource::QueryParam(name.clone())),
+		};
+
+		match token.is_empty() {
+			true => None,
+			false => Some(ExtractedToken { token, source }),
+		}
+	}
+
+	/// Whether this extractor reads the `__session` cookie, which the request state flow handles on its own.
+	pub(crate) fn is_session_cookie(&self) -> bool {
+		matches!(self, Self::Cookie(name) if name == "__session")
+	}
+}
+
+fn strip_scheme<'a>(value: &'a str, scheme: Option<&str>) -> Option<&'a str> {
+	let Some(scheme) = scheme else {
+		return Some(value);
+	};
+
+	match value.split_once(char::is_whitespace) {
+		Some((prefix, token)) if prefix.eq_ignore_ascii_case(scheme) => Some(token.trim_start()),
+		// another scheme, e.g. `Basic`
+		Some(_) => None,
+		// tokens sent without the scheme have always been accepted
+		None => Some(value),
+	}
+}
+
+/// Where an extracted token came from, with the name of the header, cookie or query param.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenSource {
+	Header(String),
+	Cookie(String),
+	QueryParam(String),
+}
+
+/// A token read from a request by a [`TokenExtractor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedToken {
+	pub token: String,
+	pub source: TokenSource,
+}
+
+/// Returns the first token found by the given extractors.
+pub fn extract_token<T: ClerkRequest>(extractors: &[TokenExtractor], request: &T) -> Option<ExtractedToken> {
+	extractors.iter().find_map(|extractor| extractor.extract(request))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::validators::request_state::tests::MockRequest;
+
+	#[test]
+	fn test_extract_header_scheme_is_case_insensitive() {
+		let request = MockRequest::default().header("Authorization", "bearer abc.def");
+
+		assert_eq!(
+			TokenExtractor::bearer().extract(&request),
+			Some(ExtractedToken {
+				token: String::from("abc.def"),
+				source: TokenSource::Header(String::from("Authorization")),
+			})
+		);
+	}
+
+	#[test]
+	fn test_extract_header_ignores_other_schemes() {
+		let basic = MockRequest::default().header("Authorization", "Basic dXNlcjpwYXNz");
+		assert_eq!(TokenExtractor::bearer().extract(&basic), None);
+
+		// the scheme is optional, as it has always been
+		let raw = MockRequest::default().header("Authorization", "abc.def");
+		assert_eq!(
+			TokenExtractor::bearer().extract(&raw).map(|extracted| extracted.token),
+			Some(String::from("abc.def"))
+		);
+	}
+
+	#[test]
+	fn test_extract_custom_header() {
+		let request = MockRequest::default().header("X-Forwarded-Token", "abc.def");
+		let extracted = TokenExtractor::header("X-Forwarded-Token", None)
+			.extract(&request)
+			.expect("should be found");
+
+		assert_eq!(extracted.token, "abc.def");
+		assert_eq!(extracted.source, TokenSource::Header(String::from("X-Forwarded-Token")));
+	}
+
+	#[test]
+	fn test_extract_query_param() {
+		let request = MockRequest::default().uri("/events?channel=1&token=abc.def");
+		let extracted = TokenExtractor::query_param("token").extract(&request).expect("should be found");
+
+		assert_eq!(extracted.token, "abc.def");
+		assert_eq!(extracted.source, TokenSource::QueryParam(String::from("token")));
+	}
+
+	#[test]
+	fn test_extract_token_is_ordered() {
+		let extractors = vec![
+			TokenExtractor::cookie("custom_session"),
+			TokenExtractor::bearer(),
+			TokenExtractor::query_param("token"),
+		];
+		let request = MockRequest::default()
+			.header("Authorization", "Bearer from_header")
+			.cookie("custom_session", "from_cookie")
+			.uri("/?token=from_query");
+
+		let extracted = extract_token(&extractors, &request).expect("should be found");
+		assert_eq!(extracted.token, "from_cookie");
+		assert_eq!(extracted.source, TokenSource::Cookie(String::from("custom_session")));
+
+		let request = MockRequest::default().uri("/?token=from_query");
+		let extracted = extract_token(&extractors, &request).expect("should be found");
+		assert_eq!(extracted.source, TokenSource::QueryParam(String::from("token")));
+
+		assert_eq!(extract_token(&extractors, &MockRequest::default().uri("/")), None);
+	}
+}
@@ -0,0 +1,406 @@
+use crate::validators::{
+	authorizer::{AuthCheck, ClerkAuthorizer, ClerkError, ClerkJwt, ClerkRequest},
+	jwks::JwksProvider,
+	request_state::RequestState,
+	session_status::SessionStatusScope,
+};
+use cookie::Cookie;
+use futures_util::future::BoxFuture;
+use http::{header::HeaderName, HeaderMap, HeaderValue, Method, Request, Response, StatusCode, Uri};
+use serde::de::DeserializeOwned;
+use std::{
+	marker::PhantomData,
+	sync::Arc,
+	task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// A [`ClerkRequest`] backed by the headers of any [`http::Request`].
+pub struct HttpClerkRequest {
+	pub headers: HeaderMap<HeaderValue>,
+	pub uri: Uri,
+	pub method: Method,
+}
+
+impl HttpClerkRequest {
+	pub fn from_request<B>(request: &Request<B>) -> Self {
+		Self {
+			headers: request.headers().clone(),
+			uri: request.uri().clone(),
+			method: request.method().clone(),
+		}
+	}
+}
+
+impl ClerkRequest for HttpClerkRequest {
+	fn get_header(&self, key: &str) -> Option<String> {
+		self.headers.get(key).and_then(|val| val.to_str().ok()).map(|val| val.to_string())
+	}
+
+	fn get_cookie(&self, key: &str) -> Option<String> {
+		self.headers
+			.get_all(http::header::COOKIE)
+			.iter()
+			.filter_map(|val| val.to_str().ok())
+			.flat_map(|val| val.split(';'))
+			.filter_map(|raw| Cookie::parse_encoded(raw.trim()).ok())
+			.find(|cookie| cookie.name() == key)
+			.map(|cookie| cookie.value().to_string())
+	}
+
+	fn get_uri(&self) -> Option<String> {
+		self.uri.path_and_query().map(|val| val.to_string())
+	}
+
+	fn get_method(&self) -> Option<String> {
+		Some(self.method.to_string())
+	}
+}
+
+/// Tower layer for protecting any `http::Request` based service with Clerk.dev.
+///
+/// The layer is not tied to a specific framework, so it can be used with hyper, tonic or a custom server. Rejected
+/// requests are answered with a response whose body is built from the error message, which is why the inner service's
+/// response body has to implement `From<String>`.
+///
+/// # Example
+/// ```
+/// let config = ClerkConfiguration::new(None, None, Some("your_secret_key".to_string()), None);
+/// let clerk = Clerk::new(config);
+///
+/// let service = ServiceBuilder::new()
+///     .layer(ClerkLayer::new(MemoryCacheJwksProvider::new(clerk), None, true))
+///     .service_fn(handler);
+/// ```
+///
+/// The verified [`ClerkJwt<C>`](crate::validators::authorizer::ClerkJwt) is added to the request extensions, where `C`
+/// are the custom claims of the authorizer, see [`ClerkAuthorizer::with_claims`].
+pub struct ClerkLayer<J, C = ()> {
+	authorizer: ClerkAuthorizer<J, C>,
+	routes: Option<Vec<String>>,
+	session_status: SessionStatusScope,
+}
+
+impl<J: JwksProvider> ClerkLayer<J> {
+	pub fn new(jwks_provider: J, routes: Option<Vec<String>>, validate_session_cookie: bool) -> Self {
+		let authorizer = ClerkAuthorizer::new(jwks_provider, validate_session_cookie);
+		Self::from_authorizer(authorizer, routes)
+	}
+}
+
+impl<J: JwksProvider, C> ClerkLayer<J, C> {
+	/// Creates the layer from a configured authorizer, e.g. one using [`ClerkAuthorizer::with_request_state`].
+	pub fn from_authorizer(authorizer: ClerkAuthorizer<J, C>, routes: Option<Vec<String>>) -> Self {
+		Self {
+			authorizer,
+			routes,
+			session_status: SessionStatusScope::default(),
+		}
+	}
+
+	/// Selects the routes that also confirm the session is still active, see [`ClerkAuthorizer::with_session_status`].
+	pub fn with_session_status(mut self, scope: SessionStatusScope) -> Self {
+		self.session_status = scope;
+		self
+	}
+}
+
+impl<S, J, C> Layer<S> for ClerkLayer<J, C> {
+	type Service = ClerkMiddleware<S, J, C>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		ClerkMiddleware {
+			service,
+			authorizer: self.authorizer.clone(),
+			routes: self.routes.clone(),
+			session_status: self.session_status.clone(),
+		}
+	}
+}
+
+impl<J, C> Clone for ClerkLayer<J, C> {
+	fn clone(&self) -> Self {
+		Self {
+			authorizer: self.authorizer.clone(),
+			routes: self.routes.clone(),
+			session_status: self.session_status.clone(),
+		}
+	}
+}
+
+pub struct ClerkMiddleware<S, J, C = ()> {
+	service: S,
+	authorizer: ClerkAuthorizer<J, C>,
+	routes: Option<Vec<String>>,
+	session_status: SessionStatusScope,
+}
+
+impl<S, J, C, ReqBody, ResBody> Service<Request<ReqBody>> for ClerkMiddleware<S, J, C>
+where
+	S: Service<Request<ReqBody>, Response = Response<ResBody>> + Send + 'static + Clone,
+	S::Future: Send + 'static,
+	J: JwksProvider + Send + Sync + 'static,
+	C: DeserializeOwned + Clone + Send + Sync + 'static,
+	ReqBody: Send + 'static,
+	ResBody: From<String>,
+{
+	type Response = S::Response;
+	type Error = S::Error;
+	type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.service.poll_ready(cx)
+	}
+
+	fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+		let mut svc = self.service.clone();
+
+		// We want to skip running the validator if we are not able to find a matching path from the listed valid paths provided by the user
+		if let Some(route_matches) = &self.routes {
+			let path = request.uri().path();
+
+			if !route_matches.iter().any(|route| route == path) {
+				// Since the path was not inside of the listed routes we want to trigger an early exit
+				return Box::pin(svc.call(request));
+			}
+		}
+
+		let authorizer = self.authorizer.clone();
+		let req = HttpClerkRequest::from_request(&request);
+		let verify_session_status = self.session_status.applies_to(request.uri().path());
+
+		Box::pin(async move {
+			match authorizer.authenticate_request_with_status(&req, verify_session_status).await {
+				// We have authed request and can pass the user onto the next body
+				RequestState { jwt: Some(jwt), headers, .. } => {
+					request.extensions_mut().insert(jwt);
+					let mut response = svc.call(request).await?;
+					// e.g. the cookies refreshed by a handshake
+					append_headers(response.headers_mut(), &headers);
+					Ok(response)
+				}
+				// Output any errors thrown from the Clerk authorizer, or the handshake the browser has to go through
+				state => Ok(state_response(state)),
+			}
+		})
+	}
+}
+
+impl<S: Clone, J, C> Clone for ClerkMiddleware<S, J, C> {
+	fn clone(&self) -> Self {
+		Self {
+			service: self.service.clone(),
+			authorizer: self.authorizer.clone(),
+			routes: self.routes.clone(),
+			session_status: self.session_status.clone(),
+		}
+	}
+}
+
+/// Tower layer that only lets requests through whose session passes an [`AuthCheck`], responding with a 403 otherwise.
+///
+/// It reads the jwt added by [`ClerkLayer`], so it has to wrap the services [`ClerkLayer`] already applies to.
+///
+/// # Example
+/// ```
+/// let service = ServiceBuilder::new()
+///     .layer(ClerkLayer::new(MemoryCacheJwksProvider::new(clerk), None, true))
+///     .layer(AuthCheckLayer::new(AuthCheck::feature("org:export")))
+///     .service_fn(handler);
+/// ```
+pub struct AuthCheckLayer<C = ()> {
+	check: Arc<AuthCheck>,
+	_claims: PhantomData<fn() -> C>,
+}
+
+impl AuthCheckLayer {
+	pub fn new(check: AuthCheck) -> Self {
+		Self {
+			check: Arc::new(check),
+			_claims: PhantomData,
+		}
+	}
+}
+
+impl<C> AuthCheckLayer<C> {
+	/// Reads the jwt of a layer using custom claims, see [`ClerkAuthorizer::with_claims`].
+	pub fn with_claims<T>(self) -> AuthCheckLayer<T> {
+		AuthCheckLayer {
+			check: self.check,
+			_claims: PhantomData,
+		}
+	}
+}
+
+impl<S, C> Layer<S> for AuthCheckLayer<C> {
+	type Service = AuthCheckMiddleware<S, C>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		AuthCheckMiddleware {
+			service,
+			check: self.check.clone(),
+			_claims: PhantomData,
+		}
+	}
+}
+
+impl<C> Clone for AuthCheckLayer<C> {
+	fn clone(&self) -> Self {
+		Self {
+			check: self.check.clone(),
+			_claims: PhantomData,
+		}
+	}
+}
+
+pub struct AuthCheckMiddleware<S, C = ()> {
+	service: S,
+	check: Arc<AuthCheck>,
+	_claims: PhantomData<fn() -> C>,
+}
+
+impl<S, C, ReqBody, ResBody> Service<Request<ReqBody>> for AuthCheckMiddleware<S, C>
+where
+	S: Service<Request<ReqBody>, Response = Response<ResBody>> + Send + 'static + Clone,
+	S::Future: Send + 'static,
+	C: Send + Sync + 'static,
+	ReqBody: Send + 'static,
+	ResBody: From<String>,
+{
+	type Response = S::Response;
+	type Error = S::Error;
+	type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.service.poll_ready(cx)
+	}
+
+	fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+		let result = match request.extensions().get::<ClerkJwt<C>>() {
+			Some(jwt) => jwt.require(&self.check),
+			None => Err(ClerkError::Unauthorized(String::from("Error: The request is not signed in!"))),
+		};
+
+		match result {
+			Ok(()) => Box::pin(self.service.call(request)),
+			// the response is built once polled, as the body is not required to be `Send`
+			Err(error) => Box::pin(async move { Ok(error_response(error)) }),
+		}
+	}
+}
+
+impl<S: Clone, C> Clone for AuthCheckMiddleware<S, C> {
+	fn clone(&self) -> Self {
+		Self {
+			service: self.service.clone(),
+			check: self.check.clone(),
+			_claims: PhantomData,
+		}
+	}
+}
+
+fn error_response<B: From<String>>(error: ClerkError) -> Response<B> {
+	let mut response = Response::new(B::from(error.message()));
+	*response.status_mut() = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::FORBIDDEN);
+	if let Some(content_type) = error.content_type() {
+		response
+			.headers_mut()
+			.insert(http::header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+	}
+	response
+}
+
+fn state_response<B: From<String>, C>(state: RequestState<C>) -> Response<B> {
+	let mut response = Response::new(B::from(state.response_body()));
+	*response.status_mut() = StatusCode::from_u16(state.status_code()).unwrap_or(StatusCode::UNAUTHORIZED);
+	append_headers(response.headers_mut(), &state.headers);
+	response
+}
+
+fn append_headers(map: &mut HeaderMap<HeaderValue>, headers: &[(String, String)]) {
+	for (key, val) in headers {
+		if let (Ok(key), Ok(val)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(val)) {
+			map.append(key, val);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_http_clerk_request_header() {
+		let request = Request::builder().header("Authorization", "Bearer token").body(()).unwrap();
+		let req = HttpClerkRequest::from_request(&request);
+
+		assert_eq!(req.get_header("authorization"), Some("Bearer token".to_string()));
+		assert_eq!(req.get_header("x-missing"), None);
+	}
+
+	#[test]
+	fn test_http_clerk_request_cookie() {
+		let request = Request::builder()
+			.header("Cookie", "theme=dark; __session=abc%2Edef")
+			.header("Cookie", "__client_uat=0")
+			.body(())
+			.unwrap();
+		let req = HttpClerkRequest::from_request(&request);
+
+		assert_eq!(req.get_cookie("__session"), Some("abc.def".to_string()));
+		assert_eq!(req.get_cookie("__client_uat"), Some("0".to_string()));
+		assert_eq!(req.get_cookie("missing"), None);
+	}
+
+	#[derive(Clone)]
+	struct OkService;
+
+	impl Service<Request<()>> for OkService {
+		type Response = Response<String>;
+		type Error = std::convert::Infallible;
+		type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+		fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+			Poll::Ready(Ok(()))
+		}
+
+		fn call(&mut self, _: Request<()>) -> Self::Future {
+			std::future::ready(Ok(Response::new(String::from("ok"))))
+		}
+	}
+
+	fn jwt() -> ClerkJwt {
+		serde_json::from_value(serde_json::json!({
+			"sub": "user",
+			"iss": "issuer",
+			"iat": 0,
+			"nbf": 0,
+			"exp": 0,
+			"org_id": "org_id",
+			"org_slug": "org_slug",
+			"org_role": "org:member",
+			"org_permissions": ["org:invoices:read"]
+		}))
+		.unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_auth_check_middleware() {
+		let mut service = AuthCheckLayer::new(AuthCheck::permission("org:invoices:read")).layer(OkService);
+
+		let mut request = Request::new(());
+		request.extensions_mut().insert(jwt());
+		let response = service.call(request).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let mut service = AuthCheckLayer::new(AuthCheck::role("org:admin")).layer(OkService);
+
+		let mut request = Request::new(());
+		request.extensions_mut().insert(jwt());
+		let response = service.call(request).await.unwrap();
+		assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+		let response = service.call(Request::new(())).await.unwrap();
+		assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+	}
+}
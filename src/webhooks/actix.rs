@@ -0,0 +1,25 @@
+use crate::webhooks::handler::{WebhookHandler, WebhookReceiver};
+use actix_web::{http::StatusCode, web, HttpRequest, HttpResponse};
+
+/// Actix handler receiving the Clerk webhooks, the [`WebhookReceiver`] is read from the app data.
+///
+/// # Example
+/// ```
+/// let receiver = web::Data::new(WebhookReceiver::new(WebhookVerifier::new(&secret)?, MyHandler));
+///
+/// HttpServer::new(move || {
+///     App::new()
+///         .app_data(receiver.clone())
+///         .route("/webhooks/clerk", web::post().to(clerk_webhook::<MyHandler>))
+/// })
+/// ```
+pub async fn clerk_webhook<H: WebhookHandler + 'static>(
+	request: HttpRequest,
+	body: web::Bytes,
+	receiver: web::Data<WebhookReceiver<H>>,
+) -> HttpResponse {
+	let get_header = |name: &str| request.headers().get(name).and_then(|val| val.to_str().ok()).map(String::from);
+	let response = receiver.receive(get_header, &body).await;
+
+	HttpResponse::build(StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)).body(response.body)
+}
@@ -0,0 +1,33 @@
+use crate::webhooks::handler::{WebhookHandler, WebhookReceiver};
+use axum::{
+	body::Bytes,
+	http::{HeaderMap, StatusCode},
+	routing::{post, MethodRouter},
+};
+use std::sync::Arc;
+
+/// Axum route receiving the Clerk webhooks.
+///
+/// # Example
+/// ```
+/// let receiver = WebhookReceiver::new(WebhookVerifier::new(&secret)?, MyHandler);
+///
+/// let app = Router::new().route("/webhooks/clerk", clerk_webhook(receiver));
+/// ```
+pub fn clerk_webhook<H, S>(receiver: WebhookReceiver<H>) -> MethodRouter<S>
+where
+	H: WebhookHandler + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	let receiver = Arc::new(receiver);
+
+	post(move |headers: HeaderMap, body: Bytes| async move {
+		let get_header = |name: &str| headers.get(name).and_then(|val| val.to_str().ok()).map(String::from);
+		let response = receiver.receive(get_header, &body).await;
+
+		(
+			StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+			response.body,
+		)
+	})
+}
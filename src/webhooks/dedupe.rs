@@ -0,0 +1,230 @@
+use crate::webhooks::handler::WebhookHandlerError;
+use async_trait::async_trait;
+use std::{
+	collections::HashMap,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+// expired entries are swept at most this often, rather than on every insert
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+// how long a claim is held when it is neither completed nor released, e.g. because the process stopped
+const DEFAULT_CLAIM_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Remembers the webhooks a [`WebhookReceiver`](crate::webhooks::handler::WebhookReceiver) processed, since Svix delivers
+/// them at least once.
+///
+/// Webhooks are keyed on their `svix-id`, which stays the same across retries. Implement this trait on top of a
+/// database to share it between several instances of a service, the [`MemoryDedupeStore`] only works within a process.
+#[async_trait]
+pub trait WebhookDedupeStore: Send + Sync {
+	/// Claims a webhook before it is dispatched, only a [`WebhookClaim::New`] claim is dispatched.
+	///
+	/// Claims should expire after a short lease, so that a webhook whose processing never completed or released it is
+	/// dispatched again when Svix retries it.
+	async fn claim(&self, message_id: &str) -> Result<WebhookClaim, WebhookHandlerError>;
+
+	/// Marks a claimed webhook as processed.
+	async fn complete(&self, message_id: &str) -> Result<(), WebhookHandlerError>;
+
+	/// Releases the claim of a webhook the handler failed to process, so that its retry is dispatched.
+	async fn release(&self, message_id: &str) -> Result<(), WebhookHandlerError>;
+
+	/// Records the `updated_at` of an object from an `*.updated` event, returning `false` if a newer version of the object
+	/// was already received. Older events are then skipped, so they never overwrite the newer version.
+	async fn record_version(&self, object_id: &str, updated_at: i64) -> Result<bool, WebhookHandlerError>;
+}
+
+/// The state of a webhook when it is claimed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookClaim {
+	/// The webhook wasn't received before, or its previous attempts failed. It is claimed until it is completed or released.
+	New,
+	/// Another delivery of the webhook is being processed. It may still fail, so this delivery has to be retried.
+	InProgress,
+	/// The webhook was processed.
+	Done,
+}
+
+struct Entry<T> {
+	value: T,
+	expires_at: Instant,
+}
+
+struct Entries<T> {
+	map: HashMap<String, Entry<T>>,
+	next_sweep: Instant,
+}
+
+impl<T> Entries<T> {
+	fn new() -> Self {
+		Self {
+			map: HashMap::new(),
+			next_sweep: Instant::now() + SWEEP_INTERVAL,
+		}
+	}
+
+	fn get(&self, key: &str) -> Option<&T> {
+		self.map
+			.get(key)
+			.filter(|entry| entry.expires_at > Instant::now())
+			.map(|entry| &entry.value)
+	}
+
+	fn insert(&mut self, key: &str, value: T, ttl: Duration) {
+		let now = Instant::now();
+		if now >= self.next_sweep {
+			self.map.retain(|_, entry| entry.expires_at > now);
+			self.next_sweep = now + SWEEP_INTERVAL;
+		}
+
+		self.map.insert(
+			key.to_string(),
+			Entry {
+				value,
+				expires_at: now + ttl,
+			},
+		);
+	}
+}
+
+/// A [`WebhookDedupeStore`] keeping the webhooks of the last `ttl` in memory.
+///
+/// The ttl should cover the retry schedule of Svix, which spans more than a day. Webhooks that are being processed are
+/// only claimed for 5 minutes, see [`with_claim_ttl`](Self::with_claim_ttl).
+pub struct MemoryDedupeStore {
+	ttl: Duration,
+	claim_ttl: Duration,
+	// whether each webhook was processed, or is being processed
+	messages: Mutex<Entries<bool>>,
+	versions: Mutex<Entries<i64>>,
+}
+
+impl MemoryDedupeStore {
+	pub fn new(ttl: Duration) -> Self {
+		Self {
+			ttl,
+			claim_ttl: DEFAULT_CLAIM_TTL,
+			messages: Mutex::new(Entries::new()),
+			versions: Mutex::new(Entries::new()),
+		}
+	}
+
+	/// Sets how long a webhook stays claimed while it is being processed, it should cover the time the handler takes.
+	pub fn with_claim_ttl(mut self, claim_ttl: Duration) -> Self {
+		self.claim_ttl = claim_ttl;
+		self
+	}
+}
+
+impl Default for MemoryDedupeStore {
+	fn default() -> Self {
+		Self::new(Duration::from_secs(3 * 24 * 60 * 60))
+	}
+}
+
+#[async_trait]
+impl WebhookDedupeStore for MemoryDedupeStore {
+	async fn claim(&self, message_id: &str) -> Result<WebhookClaim, WebhookHandlerError> {
+		let mut messages = self.messages.lock().unwrap();
+		match messages.get(message_id) {
+			Some(true) => Ok(WebhookClaim::Done),
+			Some(false) => Ok(WebhookClaim::InProgress),
+			None => {
+				messages.insert(message_id, false, self.claim_ttl.min(self.ttl));
+				Ok(WebhookClaim::New)
+			}
+		}
+	}
+
+	async fn complete(&self, message_id: &str) -> Result<(), WebhookHandlerError> {
+		let mut messages = self.messages.lock().unwrap();
+		messages.insert(message_id, true, self.ttl);
+		Ok(())
+	}
+
+	async fn release(&self, message_id: &str) -> Result<(), WebhookHandlerError> {
+		let mut messages = self.messages.lock().unwrap();
+		if messages.get(message_id) == Some(&false) {
+			messages.map.remove(message_id);
+		}
+		Ok(())
+	}
+
+	async fn record_version(&self, object_id: &str, updated_at: i64) -> Result<bool, WebhookHandlerError> {
+		let mut versions = self.versions.lock().unwrap();
+		if versions.get(object_id).is_some_and(|newest| *newest > updated_at) {
+			return Ok(false);
+		}
+
+		versions.insert(object_id, updated_at, self.ttl);
+		Ok(true)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_memory_store_claims() {
+		let store = MemoryDedupeStore::default();
+
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::InProgress);
+
+		store.release("msg_1").await.unwrap();
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+
+		store.complete("msg_1").await.unwrap();
+		store.release("msg_1").await.unwrap();
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::Done);
+	}
+
+	#[tokio::test]
+	async fn test_memory_store_claim_expiry() {
+		let store = MemoryDedupeStore::default().with_claim_ttl(Duration::ZERO);
+
+		// a claim that was never completed nor released doesn't block the retries
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+
+		store.complete("msg_1").await.unwrap();
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::Done);
+	}
+
+	#[tokio::test]
+	async fn test_memory_store_expiry() {
+		let store = MemoryDedupeStore::new(Duration::ZERO);
+
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+	}
+
+	#[test]
+	fn test_entries_sweep_on_interval() {
+		let mut entries = Entries::new();
+		entries.insert("msg_1", true, Duration::ZERO);
+		entries.insert("msg_2", true, Duration::ZERO);
+		// expired entries are kept until the next sweep
+		assert_eq!(entries.map.len(), 2);
+		assert_eq!(entries.get("msg_1"), None);
+
+		entries.next_sweep = Instant::now();
+		entries.insert("msg_3", true, Duration::from_secs(60));
+		assert_eq!(entries.map.keys().collect::<Vec<_>>(), vec!["msg_3"]);
+		assert!(entries.next_sweep > Instant::now());
+	}
+
+	#[tokio::test]
+	async fn test_memory_store_versions() {
+		let store = MemoryDedupeStore::default();
+
+		assert!(store.record_version("user_1", 2).await.unwrap());
+		assert!(!store.record_version("user_1", 1).await.unwrap());
+		// retries of the same event go through
+		assert!(store.record_version("user_1", 2).await.unwrap());
+		assert!(store.record_version("user_1", 3).await.unwrap());
+		assert!(store.record_version("user_2", 1).await.unwrap());
+	}
+}
@@ -0,0 +1,194 @@
+use crate::models::{DeletedObject, Email, Organization, OrganizationInvitation, OrganizationMembership, Session, User};
+use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
+
+macro_rules! webhook_events {
+	($(#[$meta:meta])* pub enum WebhookEvent { $($event_type:literal => $variant:ident($model:ty),)* }) => {
+		$(#[$meta])*
+		#[derive(Clone, Debug, PartialEq, Serialize)]
+		#[serde(tag = "type", content = "data")]
+		pub enum WebhookEvent {
+			$(
+				#[serde(rename = $event_type)]
+				$variant($model),
+			)*
+			/// The whole event, including its `type` and `data`.
+			#[serde(untagged)]
+			Unknown(Value),
+		}
+
+		impl WebhookEvent {
+			/// The `type` of every event with a variant, e.g. `user.created`.
+			pub const EVENT_TYPES: &'static [&'static str] = &[$($event_type),*];
+
+			/// Returns the `type` of the event, e.g. `user.created`.
+			pub fn event_type(&self) -> Option<&str> {
+				match self {
+					$(WebhookEvent::$variant(_) => Some($event_type),)*
+					WebhookEvent::Unknown(event) => event.get("type").and_then(Value::as_str),
+				}
+			}
+
+			// `None` when the type has no variant
+			fn from_data(event_type: &str, data: &Value) -> Option<Result<Self, serde_json::Error>> {
+				let event = match event_type {
+					$($event_type => <$model>::deserialize(data).map(WebhookEvent::$variant),)*
+					_ => return None,
+				};
+				Some(event)
+			}
+		}
+	};
+}
+
+webhook_events! {
+	/// A webhook sent by Clerk, tagged by its `type`, e.g. `user.created`.
+	///
+	/// Events of a type this enum doesn't know about yet are kept as the raw event in [`Unknown`](Self::Unknown) rather than
+	/// failing to parse. Events of a known type whose `data` doesn't match its model fail to parse, so they are retried.
+	///
+	/// # Example
+	/// ```
+	/// match WebhookEvent::from_slice(&body)? {
+	///     WebhookEvent::UserCreated(user) => println!("welcome {:?}", user.id),
+	///     WebhookEvent::SessionEnded(session) => println!("goodbye {}", session.user_id),
+	///     _ => {}
+	/// }
+	/// ```
+	pub enum WebhookEvent {
+		"user.created" => UserCreated(User),
+		"user.updated" => UserUpdated(User),
+		"user.deleted" => UserDeleted(DeletedObject),
+		"session.created" => SessionCreated(Session),
+		"session.ended" => SessionEnded(Session),
+		"session.removed" => SessionRemoved(Session),
+		"session.revoked" => SessionRevoked(Session),
+		"email.created" => EmailCreated(Email),
+		"organization.created" => OrganizationCreated(Organization),
+		"organization.updated" => OrganizationUpdated(Organization),
+		"organization.deleted" => OrganizationDeleted(DeletedObject),
+		"organizationMembership.created" => OrganizationMembershipCreated(OrganizationMembership),
+		"organizationMembership.updated" => OrganizationMembershipUpdated(OrganizationMembership),
+		"organizationMembership.deleted" => OrganizationMembershipDeleted(OrganizationMembership),
+		"organizationInvitation.created" => OrganizationInvitationCreated(OrganizationInvitation),
+		"organizationInvitation.accepted" => OrganizationInvitationAccepted(OrganizationInvitation),
+		"organizationInvitation.revoked" => OrganizationInvitationRevoked(OrganizationInvitation),
+	}
+}
+
+impl<'de> Deserialize<'de> for WebhookEvent {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let event = Value::deserialize(deserializer)?;
+		let known = event
+			.get("type")
+			.and_then(Value::as_str)
+			.and_then(|event_type| WebhookEvent::from_data(event_type, event.get("data").unwrap_or(&Value::Null)));
+
+		match known {
+			Some(result) => result.map_err(de::Error::custom),
+			None => Ok(WebhookEvent::Unknown(event)),
+		}
+	}
+}
+
+impl WebhookEvent {
+	/// Parses the raw body of a verified webhook.
+	pub fn from_slice(payload: &[u8]) -> Result<Self, serde_json::Error> {
+		serde_json::from_slice(payload)
+	}
+
+	/// Returns the id and `updated_at` of the object of an `*.updated` event, used to skip events received out of order.
+	pub fn object_version(&self) -> Option<(&str, i64)> {
+		match self {
+			WebhookEvent::UserUpdated(user) => user.id.as_deref().zip(user.updated_at),
+			WebhookEvent::OrganizationUpdated(organization) => Some((organization.id.as_str(), organization.updated_at)),
+			WebhookEvent::OrganizationMembershipUpdated(membership) => membership.id.as_deref().zip(membership.updated_at),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::models::session::Status;
+
+	#[test]
+	fn test_parse_user_events() {
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/user_created.json")).unwrap();
+		let WebhookEvent::UserCreated(user) = &event else {
+			panic!("expected a user.created event, got {event:?}");
+		};
+		assert_eq!(user.id.as_deref(), Some("user_29w83sxmDNGwOuEthce5gg56FcC"));
+		assert_eq!(user.email_addresses.as_ref().map(Vec::len), Some(1));
+		assert_eq!(event.event_type(), Some("user.created"));
+
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/user_deleted.json")).unwrap();
+		let WebhookEvent::UserDeleted(deleted) = event else {
+			panic!("expected a user.deleted event, got {event:?}");
+		};
+		assert!(deleted.deleted);
+		assert_eq!(deleted.id.as_deref(), Some("user_29wBMCtzATuFJut8jO2VNTVekS4"));
+	}
+
+	#[test]
+	fn test_parse_session_event() {
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/session_ended.json")).unwrap();
+		let WebhookEvent::SessionEnded(session) = event else {
+			panic!("expected a session.ended event, got {event:?}");
+		};
+		assert_eq!(session.status, Status::Ended);
+		assert_eq!(session.user_id, "user_2Azkd3r5JGZ1o9OgVqXeiBWSyWk");
+	}
+
+	#[test]
+	fn test_parse_organization_events() {
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/organization_created.json")).unwrap();
+		let WebhookEvent::OrganizationCreated(organization) = event else {
+			panic!("expected an organization.created event, got {event:?}");
+		};
+		assert_eq!(organization.slug, "acme-inc");
+
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/organization_membership_updated.json")).unwrap();
+		let WebhookEvent::OrganizationMembershipUpdated(membership) = event else {
+			panic!("expected an organizationMembership.updated event, got {event:?}");
+		};
+		assert_eq!(membership.role.as_deref(), Some("org:admin"));
+		assert_eq!(membership.organization.map(|organization| organization.name).as_deref(), Some("Acme Inc"));
+
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/organization_invitation_created.json")).unwrap();
+		let WebhookEvent::OrganizationInvitationCreated(invitation) = event else {
+			panic!("expected an organizationInvitation.created event, got {event:?}");
+		};
+		assert_eq!(invitation.email_address.as_deref(), Some("invitee@example.com"));
+	}
+
+	#[test]
+	fn test_parse_email_event() {
+		let event = WebhookEvent::from_slice(include_bytes!("fixtures/email_created.json")).unwrap();
+		let WebhookEvent::EmailCreated(email) = event else {
+			panic!("expected an email.created event, got {event:?}");
+		};
+		assert_eq!(email.slug.as_deref(), Some("verification_code"));
+		assert_eq!(email.user_id, None);
+	}
+
+	#[test]
+	fn test_parse_unknown_event() {
+		let payload = br#"{"data":{"id":"sms_1","object":"sms_message"},"object":"event","type":"sms.created"}"#;
+		let event = WebhookEvent::from_slice(payload).unwrap();
+
+		assert!(matches!(event, WebhookEvent::Unknown(_)));
+		assert_eq!(event.event_type(), Some("sms.created"));
+	}
+
+	#[test]
+	fn test_parse_known_event_with_invalid_data() {
+		// a known type is never passed on as unknown, its handler would silently miss it
+		let payload = br#"{"data":{"id":"sess_1","object":"session"},"object":"event","type":"session.ended"}"#;
+		assert!(WebhookEvent::from_slice(payload).is_err());
+
+		let payload = br#"{"object":"event","type":"user.created"}"#;
+		assert!(WebhookEvent::from_slice(payload).is_err());
+	}
+}
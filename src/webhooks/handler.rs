@@ -0,0 +1,540 @@
+use crate::{
+	models::{DeletedObject, Email, Organization, OrganizationInvitation, OrganizationMembership, Session, User},
+	webhooks::{
+		dedupe::{WebhookClaim, WebhookDedupeStore},
+		event::WebhookEvent,
+		verify::{WebhookHeaders, WebhookVerifier},
+	},
+};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::{error::Error, future::Future, sync::Arc};
+
+/// The error a [`WebhookHandler`] fails with, the webhook is answered with a 500 so that Svix retries it.
+pub type WebhookHandlerError = Box<dyn Error + Send + Sync>;
+
+/// Handles the verified webhooks of a [`WebhookReceiver`].
+///
+/// Every event type has a method that does nothing by default, so handlers only implement the events they care about.
+/// Handlers that would rather match on the events themselves can implement [`on_event`](Self::on_event) instead.
+#[async_trait]
+pub trait WebhookHandler: Send + Sync {
+	async fn on_event(&self, event: WebhookEvent) -> Result<(), WebhookHandlerError> {
+		match event {
+			WebhookEvent::UserCreated(user) => self.on_user_created(user).await,
+			WebhookEvent::UserUpdated(user) => self.on_user_updated(user).await,
+			WebhookEvent::UserDeleted(deleted) => self.on_user_deleted(deleted).await,
+			WebhookEvent::SessionCreated(session) => self.on_session_created(session).await,
+			WebhookEvent::SessionEnded(session) => self.on_session_ended(session).await,
+			WebhookEvent::SessionRemoved(session) => self.on_session_removed(session).await,
+			WebhookEvent::SessionRevoked(session) => self.on_session_revoked(session).await,
+			WebhookEvent::EmailCreated(email) => self.on_email_created(email).await,
+			WebhookEvent::OrganizationCreated(organization) => self.on_organization_created(organization).await,
+			WebhookEvent::OrganizationUpdated(organization) => self.on_organization_updated(organization).await,
+			WebhookEvent::OrganizationDeleted(deleted) => self.on_organization_deleted(deleted).await,
+			WebhookEvent::OrganizationMembershipCreated(membership) => self.on_organization_membership_created(membership).await,
+			WebhookEvent::OrganizationMembershipUpdated(membership) => self.on_organization_membership_updated(membership).await,
+			WebhookEvent::OrganizationMembershipDeleted(membership) => self.on_organization_membership_deleted(membership).await,
+			WebhookEvent::OrganizationInvitationCreated(invitation) => self.on_organization_invitation_created(invitation).await,
+			WebhookEvent::OrganizationInvitationAccepted(invitation) => self.on_organization_invitation_accepted(invitation).await,
+			WebhookEvent::OrganizationInvitationRevoked(invitation) => self.on_organization_invitation_revoked(invitation).await,
+			WebhookEvent::Unknown(event) => self.on_unknown(event).await,
+		}
+	}
+
+	async fn on_user_created(&self, _user: User) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_user_updated(&self, _user: User) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_user_deleted(&self, _deleted: DeletedObject) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_session_created(&self, _session: Session) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_session_ended(&self, _session: Session) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_session_removed(&self, _session: Session) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_session_revoked(&self, _session: Session) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_email_created(&self, _email: Email) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_created(&self, _organization: Organization) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_updated(&self, _organization: Organization) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_deleted(&self, _deleted: DeletedObject) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_membership_created(&self, _membership: OrganizationMembership) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_membership_updated(&self, _membership: OrganizationMembership) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_membership_deleted(&self, _membership: OrganizationMembership) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_invitation_created(&self, _invitation: OrganizationInvitation) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_invitation_accepted(&self, _invitation: OrganizationInvitation) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	async fn on_organization_invitation_revoked(&self, _invitation: OrganizationInvitation) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+
+	/// Events of a type the crate doesn't know about yet, with their `type` and `data`.
+	async fn on_unknown(&self, _event: Value) -> Result<(), WebhookHandlerError> {
+		Ok(())
+	}
+}
+
+/// A [`WebhookHandler`] calling an async closure with every event, see [`WebhookReceiver::from_fn`].
+pub struct WebhookHandlerFn<F>(F);
+
+#[async_trait]
+impl<F, Fut> WebhookHandler for WebhookHandlerFn<F>
+where
+	F: Fn(WebhookEvent) -> Fut + Send + Sync,
+	Fut: Future<Output = Result<(), WebhookHandlerError>> + Send,
+{
+	async fn on_event(&self, event: WebhookEvent) -> Result<(), WebhookHandlerError> {
+		(self.0)(event).await
+	}
+}
+
+/// What a webhook endpoint responds with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookResponse {
+	pub status: u16,
+	pub body: String,
+}
+
+impl WebhookResponse {
+	fn new(status: u16, body: &str) -> Self {
+		Self {
+			status,
+			body: body.to_string(),
+		}
+	}
+}
+
+/// Verifies, parses and dispatches the webhooks received by the endpoints of the framework modules.
+///
+/// Webhooks that fail verification or parsing are answered with a 400, webhooks the handler fails to process with a 500 so
+/// that Svix retries them, and the others with a 204. With a dedupe store, deliveries of a webhook that is still being
+/// processed are answered with a 409, so that Svix retries them in case the processing fails.
+pub struct WebhookReceiver<H> {
+	verifier: WebhookVerifier,
+	handler: H,
+	dedupe_store: Option<Arc<dyn WebhookDedupeStore>>,
+}
+
+impl<H: WebhookHandler> WebhookReceiver<H> {
+	pub fn new(verifier: WebhookVerifier, handler: H) -> Self {
+		Self {
+			verifier,
+			handler,
+			dedupe_store: None,
+		}
+	}
+
+	/// Dispatches each webhook once, even when Svix delivers it again, and skips `*.updated` events older than the last
+	/// version of their object received.
+	pub fn with_dedupe_store<D: WebhookDedupeStore + 'static>(mut self, store: D) -> Self {
+		self.dedupe_store = Some(Arc::new(store));
+		self
+	}
+
+	/// Returns a reference to the underlying [`WebhookHandler`].
+	pub fn handler(&self) -> &H {
+		&self.handler
+	}
+
+	/// Processes a webhook from its headers, looked up with `get_header`, and its raw body.
+	pub async fn receive<F>(&self, get_header: F, payload: &[u8]) -> WebhookResponse
+	where
+		F: Fn(&str) -> Option<String>,
+	{
+		let verified = WebhookHeaders::from_fn(get_header).and_then(|headers| self.verifier.verify(&headers, payload).map(|_| headers));
+		let headers = match verified {
+			Ok(headers) => headers,
+			Err(error) => {
+				log::warn!("clerk-rs: rejected a webhook: {}", error);
+				return WebhookResponse::new(400, &error.to_string());
+			}
+		};
+
+		let event = match WebhookEvent::from_slice(payload) {
+			Ok(event) => event,
+			Err(error) => {
+				log::warn!("clerk-rs: could not parse a webhook: {}", error);
+				return WebhookResponse::new(400, "Error: Invalid webhook payload!");
+			}
+		};
+
+		let result = match &self.dedupe_store {
+			Some(store) => self.dispatch_once(store, &headers.id, event).await,
+			None => self.handler.on_event(event).await.map(|_| WebhookClaim::New),
+		};
+
+		match result {
+			Ok(WebhookClaim::InProgress) => {
+				log::debug!("clerk-rs: the webhook {} is already being processed", headers.id);
+				WebhookResponse::new(409, "Error: The webhook is already being processed!")
+			}
+			Ok(_) => WebhookResponse::new(204, ""),
+			Err(error) => {
+				log::error!("clerk-rs: the webhook handler failed: {}", error);
+				WebhookResponse::new(500, "Error: The webhook could not be processed!")
+			}
+		}
+	}
+
+	// returns how the webhook was claimed, it was only dispatched when it was new
+	async fn dispatch_once(
+		&self,
+		store: &Arc<dyn WebhookDedupeStore>,
+		message_id: &str,
+		event: WebhookEvent,
+	) -> Result<WebhookClaim, WebhookHandlerError> {
+		match store.claim(message_id).await? {
+			WebhookClaim::New => {}
+			WebhookClaim::InProgress => return Ok(WebhookClaim::InProgress),
+			WebhookClaim::Done => {
+				log::debug!("clerk-rs: skipping the webhook {}, it was already processed", message_id);
+				return Ok(WebhookClaim::Done);
+			}
+		}
+
+		// the request may be cancelled while the handler runs, which must not leave the webhook claimed
+		let claim = ClaimGuard {
+			store: store.clone(),
+			message_id: Some(message_id.to_string()),
+		};
+
+		let result = match event.object_version() {
+			Some((object_id, updated_at)) => match store.record_version(object_id, updated_at).await {
+				Ok(true) => self.handler.on_event(event).await,
+				Ok(false) => {
+					log::debug!(
+						"clerk-rs: skipping the webhook {}, a newer version of {} was received",
+						message_id,
+						object_id
+					);
+					Ok(())
+				}
+				Err(error) => Err(error),
+			},
+			None => self.handler.on_event(event).await,
+		};
+
+		let result = match result {
+			Ok(()) => store.complete(message_id).await.map(|_| WebhookClaim::New),
+			Err(error) => {
+				store.release(message_id).await?;
+				Err(error)
+			}
+		};
+		claim.disarm();
+		result
+	}
+}
+
+// Releases the claim of a webhook whose dispatch is dropped before it finished.
+struct ClaimGuard {
+	store: Arc<dyn WebhookDedupeStore>,
+	message_id: Option<String>,
+}
+
+impl ClaimGuard {
+	fn disarm(mut self) {
+		self.message_id = None;
+	}
+}
+
+impl Drop for ClaimGuard {
+	fn drop(&mut self) {
+		let Some(message_id) = self.message_id.take() else {
+			return;
+		};
+
+		// without a runtime to release it on, the claim is left to expire
+		if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+			let store = self.store.clone();
+			runtime.spawn(async move {
+				if let Err(error) = store.release(&message_id).await {
+					log::warn!("clerk-rs: could not release the webhook {}: {}", message_id, error);
+				}
+			});
+		}
+	}
+}
+
+impl<F, Fut> WebhookReceiver<WebhookHandlerFn<F>>
+where
+	F: Fn(WebhookEvent) -> Fut + Send + Sync,
+	Fut: Future<Output = Result<(), WebhookHandlerError>> + Send,
+{
+	/// Creates a receiver calling an async closure with every event.
+	///
+	/// # Example
+	/// ```
+	/// let receiver = WebhookReceiver::from_fn(verifier, |event| async move {
+	///     if let WebhookEvent::UserCreated(user) = event {
+	///         create_account(user).await?;
+	///     }
+	///     Ok(())
+	/// });
+	/// ```
+	pub fn from_fn(verifier: WebhookVerifier, handler: F) -> Self {
+		Self::new(verifier, WebhookHandlerFn(handler))
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::webhooks::{
+		dedupe::MemoryDedupeStore,
+		verify::{SVIX_ID_HEADER, SVIX_SIGNATURE_HEADER, SVIX_TIMESTAMP_HEADER},
+	};
+	use std::{
+		collections::HashMap,
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			Mutex,
+		},
+		time::{SystemTime, UNIX_EPOCH},
+	};
+	use tokio::sync::Notify;
+
+	pub(crate) const SECRET: &str = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+
+	/// Records the users created and updated, and fails on user deletions.
+	#[derive(Default)]
+	pub(crate) struct RecordingHandler {
+		pub(crate) users: Mutex<Vec<String>>,
+		pub(crate) updates: Mutex<Vec<i64>>,
+	}
+
+	#[async_trait]
+	impl WebhookHandler for RecordingHandler {
+		async fn on_user_created(&self, user: User) -> Result<(), WebhookHandlerError> {
+			self.users.lock().unwrap().push(user.id.unwrap_or_default());
+			Ok(())
+		}
+
+		async fn on_user_updated(&self, user: User) -> Result<(), WebhookHandlerError> {
+			self.updates.lock().unwrap().push(user.updated_at.unwrap_or_default());
+			Ok(())
+		}
+
+		async fn on_user_deleted(&self, _deleted: DeletedObject) -> Result<(), WebhookHandlerError> {
+			Err("the database is down".into())
+		}
+	}
+
+	/// The svix headers of a payload signed with [`SECRET`] now.
+	pub(crate) fn signed_headers(message_id: &str, payload: &[u8]) -> HashMap<&'static str, String> {
+		let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+		let signature = WebhookVerifier::new(SECRET).unwrap().sign(message_id, timestamp, payload);
+
+		HashMap::from([
+			(SVIX_ID_HEADER, message_id.to_string()),
+			(SVIX_TIMESTAMP_HEADER, timestamp.to_string()),
+			(SVIX_SIGNATURE_HEADER, signature),
+		])
+	}
+
+	#[tokio::test]
+	async fn test_receive_dispatches_events() {
+		let receiver = WebhookReceiver::new(WebhookVerifier::new(SECRET).unwrap(), RecordingHandler::default());
+		let payload = include_bytes!("fixtures/user_created.json");
+		let headers = signed_headers("msg_1", payload);
+
+		let response = receiver.receive(|name| headers.get(name).cloned(), payload).await;
+		assert_eq!(response.status, 204);
+		assert_eq!(*receiver.handler().users.lock().unwrap(), vec!["user_29w83sxmDNGwOuEthce5gg56FcC"]);
+	}
+
+	#[tokio::test]
+	async fn test_receive_rejects_invalid_signatures() {
+		let receiver = WebhookReceiver::new(WebhookVerifier::new(SECRET).unwrap(), RecordingHandler::default());
+		let payload = include_bytes!("fixtures/user_created.json");
+		let mut headers = signed_headers("msg_1", b"{}");
+
+		let response = receiver.receive(|name| headers.get(name).cloned(), payload).await;
+		assert_eq!(response.status, 400);
+		assert!(receiver.handler().users.lock().unwrap().is_empty());
+
+		headers.remove(SVIX_SIGNATURE_HEADER);
+		let response = receiver.receive(|name| headers.get(name).cloned(), payload).await;
+		assert_eq!(response.status, 400);
+	}
+
+	#[tokio::test]
+	async fn test_receive_handler_errors() {
+		let receiver = WebhookReceiver::new(WebhookVerifier::new(SECRET).unwrap(), RecordingHandler::default());
+		let payload = include_bytes!("fixtures/user_deleted.json");
+		let headers = signed_headers("msg_1", payload);
+
+		let response = receiver.receive(|name| headers.get(name).cloned(), payload).await;
+		assert_eq!(response.status, 500);
+	}
+
+	#[tokio::test]
+	async fn test_receive_dispatches_once() {
+		let receiver =
+			WebhookReceiver::new(WebhookVerifier::new(SECRET).unwrap(), RecordingHandler::default()).with_dedupe_store(MemoryDedupeStore::default());
+		let payload = include_bytes!("fixtures/user_created.json");
+		let headers = signed_headers("msg_1", payload);
+
+		for _ in 0..2 {
+			let response = receiver.receive(|name| headers.get(name).cloned(), payload).await;
+			assert_eq!(response.status, 204);
+		}
+		assert_eq!(receiver.handler().users.lock().unwrap().len(), 1);
+
+		// failed webhooks are dispatched again when retried
+		let payload = include_bytes!("fixtures/user_deleted.json");
+		let headers = signed_headers("msg_2", payload);
+		for _ in 0..2 {
+			let response = receiver.receive(|name| headers.get(name).cloned(), payload).await;
+			assert_eq!(response.status, 500);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_receive_retries_while_in_progress() {
+		let started = Arc::new(Notify::new());
+		let resume = Arc::new(Notify::new());
+		let attempts = Arc::new(AtomicUsize::new(0));
+		let handler = {
+			let (started, resume, attempts) = (started.clone(), resume.clone(), attempts.clone());
+			move |_event| {
+				let (started, resume, attempts) = (started.clone(), resume.clone(), attempts.clone());
+				async move {
+					// the first attempt fails after another delivery came in
+					if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+						started.notify_one();
+						resume.notified().await;
+						return Err(WebhookHandlerError::from("the database is down"));
+					}
+					Ok(())
+				}
+			}
+		};
+		let receiver = WebhookReceiver::from_fn(WebhookVerifier::new(SECRET).unwrap(), handler).with_dedupe_store(MemoryDedupeStore::default());
+		let payload = include_bytes!("fixtures/user_created.json");
+		let headers = signed_headers("msg_1", payload);
+		let receive = || receiver.receive(|name| headers.get(name).cloned(), payload);
+
+		let concurrent = async {
+			started.notified().await;
+			let response = receive().await;
+			resume.notify_one();
+			response
+		};
+		let (first, concurrent) = tokio::join!(receive(), concurrent);
+		assert_eq!(concurrent.status, 409);
+		assert_eq!(first.status, 500);
+
+		// the retry is dispatched, and answered without dispatching once it succeeded
+		assert_eq!(receive().await.status, 204);
+		assert_eq!(receive().await.status, 204);
+		assert_eq!(attempts.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn test_receive_releases_cancelled_dispatch() {
+		let started = Arc::new(Notify::new());
+		let handler = {
+			let started = started.clone();
+			move |_event| {
+				let started = started.clone();
+				async move {
+					started.notify_one();
+					// never finishes, like a handler whose request timed out
+					std::future::pending::<()>().await;
+					Ok(())
+				}
+			}
+		};
+		let receiver = WebhookReceiver::from_fn(WebhookVerifier::new(SECRET).unwrap(), handler).with_dedupe_store(MemoryDedupeStore::default());
+		let payload = include_bytes!("fixtures/user_created.json");
+		let headers = signed_headers("msg_1", payload);
+
+		tokio::select! {
+			_ = receiver.receive(|name| headers.get(name).cloned(), payload) => panic!("the handler should not finish"),
+			_ = started.notified() => {}
+		}
+		// let the release spawned by the dropped dispatch run
+		tokio::task::yield_now().await;
+
+		let store = receiver.dedupe_store.as_ref().unwrap();
+		assert_eq!(store.claim("msg_1").await.unwrap(), WebhookClaim::New);
+	}
+
+	#[tokio::test]
+	async fn test_receive_skips_older_updates() {
+		let receiver =
+			WebhookReceiver::new(WebhookVerifier::new(SECRET).unwrap(), RecordingHandler::default()).with_dedupe_store(MemoryDedupeStore::default());
+
+		let user_updated = |updated_at: i64| {
+			let mut event: Value = serde_json::from_slice(include_bytes!("fixtures/user_created.json")).unwrap();
+			event["type"] = Value::from("user.updated");
+			event["data"]["updated_at"] = Value::from(updated_at);
+			serde_json::to_vec(&event).unwrap()
+		};
+
+		for (message_id, updated_at) in [("msg_1", 20), ("msg_2", 10), ("msg_3", 30)] {
+			let payload = user_updated(updated_at);
+			let headers = signed_headers(message_id, &payload);
+			let response = receiver.receive(|name| headers.get(name).cloned(), &payload).await;
+			assert_eq!(response.status, 204);
+		}
+		assert_eq!(*receiver.handler().updates.lock().unwrap(), vec![20, 30]);
+	}
+
+	#[tokio::test]
+	async fn test_receive_from_fn() {
+		let receiver = WebhookReceiver::from_fn(WebhookVerifier::new(SECRET).unwrap(), |event| async move {
+			match event.event_type() {
+				Some("user.created") => Ok(()),
+				_ => Err(WebhookHandlerError::from("unexpected event")),
+			}
+		});
+		let payload = include_bytes!("fixtures/user_created.json");
+		let headers = signed_headers("msg_1", payload);
+
+		assert_eq!(receiver.receive(|name| headers.get(name).cloned(), payload).await.status, 204);
+	}
+}
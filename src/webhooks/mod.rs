@@ -0,0 +1,15 @@
+pub mod dedupe;
+pub mod event;
+pub mod handler;
+pub mod testing;
+pub mod verify;
+
+// Framework-specific modules
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "poem")]
+pub mod poem;
+#[cfg(feature = "rocket")]
+pub mod rocket;
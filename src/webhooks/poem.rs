@@ -0,0 +1,37 @@
+use crate::webhooks::handler::{WebhookHandler, WebhookReceiver};
+use poem::{http::StatusCode, Endpoint, Request, Response, Result};
+use std::sync::Arc;
+
+/// Poem endpoint receiving the Clerk webhooks.
+///
+/// # Example
+/// ```
+/// let receiver = WebhookReceiver::new(WebhookVerifier::new(&secret)?, MyHandler);
+///
+/// let app = Route::new().at("/webhooks/clerk", post(ClerkPoemWebhook::new(receiver)));
+/// ```
+pub struct ClerkPoemWebhook<H> {
+	receiver: Arc<WebhookReceiver<H>>,
+}
+
+impl<H: WebhookHandler> ClerkPoemWebhook<H> {
+	pub fn new(receiver: WebhookReceiver<H>) -> Self {
+		Self {
+			receiver: Arc::new(receiver),
+		}
+	}
+}
+
+impl<H: WebhookHandler + 'static> Endpoint for ClerkPoemWebhook<H> {
+	type Output = Response;
+
+	async fn call(&self, mut req: Request) -> Result<Self::Output> {
+		let body = req.take_body().into_vec().await?;
+		let get_header = |name: &str| req.header(name).map(String::from);
+		let response = self.receiver.receive(get_header, &body).await;
+
+		Ok(Response::builder()
+			.status(StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+			.body(response.body))
+	}
+}
@@ -0,0 +1,58 @@
+use crate::webhooks::handler::{WebhookHandler, WebhookReceiver};
+use rocket::{
+	data::{Data, ToByteUnit},
+	http::{Method, Status},
+	route::{Handler, Outcome, Route},
+	Request,
+};
+use std::sync::Arc;
+
+/// Rocket handler receiving the Clerk webhooks.
+///
+/// # Example
+/// ```
+/// let receiver = WebhookReceiver::new(WebhookVerifier::new(&secret)?, MyHandler);
+///
+/// rocket::build().mount("/", vec![ClerkWebhookHandler::new(receiver).route("/webhooks/clerk")])
+/// ```
+pub struct ClerkWebhookHandler<H> {
+	receiver: Arc<WebhookReceiver<H>>,
+}
+
+impl<H: WebhookHandler + 'static> ClerkWebhookHandler<H> {
+	pub fn new(receiver: WebhookReceiver<H>) -> Self {
+		Self {
+			receiver: Arc::new(receiver),
+		}
+	}
+
+	/// A `POST` route on the given path.
+	pub fn route(self, path: &str) -> Route {
+		Route::new(Method::Post, path, self)
+	}
+}
+
+impl<H> Clone for ClerkWebhookHandler<H> {
+	fn clone(&self) -> Self {
+		Self {
+			receiver: self.receiver.clone(),
+		}
+	}
+}
+
+#[rocket::async_trait]
+impl<H: WebhookHandler + 'static> Handler for ClerkWebhookHandler<H> {
+	async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+		let limit = request.limits().get("json").unwrap_or(1.mebibytes());
+		let body = match data.open(limit).into_bytes().await {
+			Ok(body) if body.is_complete() => body.into_inner(),
+			Ok(_) => return Outcome::Error(Status::PayloadTooLarge),
+			Err(_) => return Outcome::Error(Status::BadRequest),
+		};
+
+		let get_header = |name: &str| request.headers().get_one(name).map(String::from);
+		let response = self.receiver.receive(get_header, &body).await;
+
+		Outcome::from(request, (Status::new(response.status), response.body))
+	}
+}
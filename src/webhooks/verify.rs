@@ -0,0 +1,256 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{
+	error::Error,
+	fmt,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const SVIX_ID_HEADER: &str = "svix-id";
+pub const SVIX_TIMESTAMP_HEADER: &str = "svix-timestamp";
+pub const SVIX_SIGNATURE_HEADER: &str = "svix-signature";
+
+const SECRET_PREFIX: &str = "whsec_";
+const SIGNATURE_VERSION: &str = "v1";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebhookError {
+	/// One of the `svix-*` headers is missing.
+	MissingHeader(&'static str),
+	/// The signing secret is not a valid `whsec_` secret.
+	InvalidSecret,
+	/// The `svix-timestamp` header is not a unix timestamp.
+	InvalidTimestamp,
+	/// The timestamp is further away from now than the tolerance, which protects against replayed webhooks.
+	TimestampOutOfTolerance,
+	/// None of the signatures match the payload.
+	InvalidSignature,
+}
+
+impl fmt::Display for WebhookError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			WebhookError::MissingHeader(header) => write!(f, "Error: Missing the {} header!", header),
+			WebhookError::InvalidSecret => write!(f, "Error: Invalid webhook signing secret!"),
+			WebhookError::InvalidTimestamp => write!(f, "Error: Invalid webhook timestamp!"),
+			WebhookError::TimestampOutOfTolerance => write!(f, "Error: The webhook timestamp is out of tolerance!"),
+			WebhookError::InvalidSignature => write!(f, "Error: Invalid webhook signature!"),
+		}
+	}
+}
+
+impl Error for WebhookError {}
+
+/// The `svix-*` headers of a webhook request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookHeaders {
+	pub id: String,
+	pub timestamp: String,
+	/// Space separated `v1,<signature>` entries, one per active signing secret.
+	pub signature: String,
+}
+
+impl WebhookHeaders {
+	/// Reads the headers with the given lookup, e.g. `WebhookHeaders::from_fn(|name| request.header(name))`.
+	pub fn from_fn<F>(get_header: F) -> Result<Self, WebhookError>
+	where
+		F: Fn(&str) -> Option<String>,
+	{
+		let header = |name: &'static str| get_header(name).ok_or(WebhookError::MissingHeader(name));
+
+		Ok(Self {
+			id: header(SVIX_ID_HEADER)?,
+			timestamp: header(SVIX_TIMESTAMP_HEADER)?,
+			signature: header(SVIX_SIGNATURE_HEADER)?,
+		})
+	}
+}
+
+/// Verifies the Svix signatures of the webhooks Clerk sends.
+///
+/// While a signing secret is being rotated, Svix signs webhooks with both the old and the new secret, and either one is
+/// enough to verify them. Several secrets can also be configured with [`with_secret`](Self::with_secret).
+#[derive(Clone, Debug)]
+pub struct WebhookVerifier {
+	keys: Vec<Vec<u8>>,
+	tolerance: Duration,
+}
+
+impl WebhookVerifier {
+	/// Creates a verifier for the `whsec_...` signing secret of a webhook endpoint.
+	pub fn new(secret: &str) -> Result<Self, WebhookError> {
+		Ok(Self {
+			keys: vec![decode_secret(secret)?],
+			tolerance: Duration::from_secs(5 * 60),
+		})
+	}
+
+	/// Accepts webhooks signed with another secret as well, e.g. the previous one during a rotation.
+	pub fn with_secret(mut self, secret: &str) -> Result<Self, WebhookError> {
+		self.keys.push(decode_secret(secret)?);
+		Ok(self)
+	}
+
+	/// Sets how far the timestamp of a webhook can be from now, 5 minutes by default.
+	pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+		self.tolerance = tolerance;
+		self
+	}
+
+	/// Verifies the signature and the timestamp of a webhook against its raw body.
+	pub fn verify(&self, headers: &WebhookHeaders, payload: &[u8]) -> Result<(), WebhookError> {
+		self.verify_at(headers, payload, SystemTime::now())
+	}
+
+	/// Verifies a webhook as if it was received at `now`.
+	pub fn verify_at(&self, headers: &WebhookHeaders, payload: &[u8], now: SystemTime) -> Result<(), WebhookError> {
+		let timestamp = headers.timestamp.trim().parse::<u64>().map_err(|_| WebhookError::InvalidTimestamp)?;
+		let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+		if now.abs_diff(timestamp) > self.tolerance.as_secs() {
+			return Err(WebhookError::TimestampOutOfTolerance);
+		}
+
+		let signatures: Vec<Vec<u8>> = headers
+			.signature
+			.split_whitespace()
+			.filter_map(|entry| entry.split_once(','))
+			.filter(|(version, _)| *version == SIGNATURE_VERSION)
+			.filter_map(|(_, signature)| STANDARD.decode(signature).ok())
+			.collect();
+
+		let verified = self.keys.iter().any(|key| {
+			signatures.iter().any(|signature| {
+				// constant time comparison
+				signing_mac(key, &headers.id, &headers.timestamp, payload).verify_slice(signature).is_ok()
+			})
+		});
+
+		match verified {
+			true => Ok(()),
+			false => Err(WebhookError::InvalidSignature),
+		}
+	}
+
+	/// Signs a payload with the first secret, returning the value of the `svix-signature` header.
+	pub fn sign(&self, id: &str, timestamp: u64, payload: &[u8]) -> String {
+		let mac = signing_mac(&self.keys[0], id, &timestamp.to_string(), payload);
+		format!("{},{}", SIGNATURE_VERSION, STANDARD.encode(mac.finalize().into_bytes()))
+	}
+}
+
+/// Verifies a webhook with a single signing secret and the default tolerance.
+pub fn verify(secret: &str, headers: &WebhookHeaders, payload: &[u8]) -> Result<(), WebhookError> {
+	WebhookVerifier::new(secret)?.verify(headers, payload)
+}
+
+fn decode_secret(secret: &str) -> Result<Vec<u8>, WebhookError> {
+	let secret = secret.strip_prefix(SECRET_PREFIX).unwrap_or(secret);
+	STANDARD.decode(secret).map_err(|_| WebhookError::InvalidSecret)
+}
+
+fn signing_mac(key: &[u8], id: &str, timestamp: &str, payload: &[u8]) -> Hmac<Sha256> {
+	let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+	mac.update(id.as_bytes());
+	mac.update(b".");
+	mac.update(timestamp.as_bytes());
+	mac.update(b".");
+	mac.update(payload);
+	mac
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The example from the Svix documentation
+	const SECRET: &str = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+	const ID: &str = "msg_p5jXN8AQM9LWM0D4loKWxJek";
+	const TIMESTAMP: u64 = 1614265330;
+	const PAYLOAD: &[u8] = br#"{"test": 2432232314}"#;
+	const SIGNATURE: &str = "v1,g0hM9SsE+OTPJTGt/tmIKtSyZlE3uFJELVlNIOLJ1OE=";
+
+	fn headers(signature: &str) -> WebhookHeaders {
+		WebhookHeaders {
+			id: ID.to_string(),
+			timestamp: TIMESTAMP.to_string(),
+			signature: signature.to_string(),
+		}
+	}
+
+	fn received_after(secs: u64) -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(TIMESTAMP + secs)
+	}
+
+	#[test]
+	fn test_verify_svix_example() {
+		let verifier = WebhookVerifier::new(SECRET).unwrap();
+
+		assert_eq!(verifier.sign(ID, TIMESTAMP, PAYLOAD), SIGNATURE);
+		assert_eq!(verifier.verify_at(&headers(SIGNATURE), PAYLOAD, received_after(10)), Ok(()));
+		assert_eq!(
+			verifier.verify_at(&headers(SIGNATURE), br#"{"test": 2432232315}"#, received_after(10)),
+			Err(WebhookError::InvalidSignature)
+		);
+	}
+
+	#[test]
+	fn test_verify_multiple_signatures() {
+		let verifier = WebhookVerifier::new(SECRET).unwrap();
+
+		// signed with a rotated secret as well, and a signature version that isn't supported
+		let signature = format!("v1,Ceo5qEr07ixe2NLpvHk3FH9bwy/WavXrAFQ/9tdO6mc= v2,abc {SIGNATURE}");
+		assert_eq!(verifier.verify_at(&headers(&signature), PAYLOAD, received_after(10)), Ok(()));
+
+		assert_eq!(
+			verifier.verify_at(&headers("v2,g0hM9SsE+OTPJTGt/tmIKtSyZlE3uFJELVlNIOLJ1OE="), PAYLOAD, received_after(10)),
+			Err(WebhookError::InvalidSignature)
+		);
+	}
+
+	#[test]
+	fn test_verify_secret_rotation() {
+		let verifier = WebhookVerifier::new("whsec_dGhlIG5ldyBzaWduaW5nIHNlY3JldA==")
+			.unwrap()
+			.with_secret(SECRET)
+			.unwrap();
+
+		assert_eq!(verifier.verify_at(&headers(SIGNATURE), PAYLOAD, received_after(10)), Ok(()));
+		assert_eq!(WebhookVerifier::new("whsec_not base64!").err(), Some(WebhookError::InvalidSecret));
+	}
+
+	#[test]
+	fn test_verify_timestamp_tolerance() {
+		let verifier = WebhookVerifier::new(SECRET).unwrap();
+
+		assert_eq!(
+			verifier.verify_at(&headers(SIGNATURE), PAYLOAD, received_after(6 * 60)),
+			Err(WebhookError::TimestampOutOfTolerance)
+		);
+		assert_eq!(
+			verifier.verify_at(&headers(SIGNATURE), PAYLOAD, UNIX_EPOCH + Duration::from_secs(TIMESTAMP - 6 * 60)),
+			Err(WebhookError::TimestampOutOfTolerance)
+		);
+
+		let lenient = verifier.with_tolerance(Duration::from_secs(10 * 60));
+		assert_eq!(lenient.verify_at(&headers(SIGNATURE), PAYLOAD, received_after(6 * 60)), Ok(()));
+
+		let mut invalid = headers(SIGNATURE);
+		invalid.timestamp = String::from("yesterday");
+		assert_eq!(
+			lenient.verify_at(&invalid, PAYLOAD, received_after(0)),
+			Err(WebhookError::InvalidTimestamp)
+		);
+	}
+
+	#[test]
+	fn test_webhook_headers_from_fn() {
+		let headers = WebhookHeaders::from_fn(|name| match name {
+			SVIX_ID_HEADER => Some(ID.to_string()),
+			SVIX_TIMESTAMP_HEADER => Some(TIMESTAMP.to_string()),
+			_ => None,
+		});
+
+		assert_eq!(headers, Err(WebhookError::MissingHeader(SVIX_SIGNATURE_HEADER)));
+	}
+}